@@ -0,0 +1,487 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, AccountDeserialize, Discriminator,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use anyhow::{anyhow, bail, Context, Result};
+use raffle_client::{
+    decode_account, instructions,
+    pda::{
+        find_config_address, find_counter_address, find_participant_page_address,
+        find_raffle_address,
+    },
+    types::{
+        ConfigParams, Counter, DisputeResolution, ParticipantPage, RaffleAccount, RaffleStatus,
+        RandomnessSource,
+    },
+    ID,
+};
+use sha2::{Digest, Sha256};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionAccountsConfig,
+        RpcSimulateTransactionConfig,
+    },
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::{
+    item::ItemFile,
+    output::{print, Format, RaffleRow, RaffleView, TxOutput},
+    reveal,
+};
+
+/// Offset of `RaffleAccount::seller`: discriminator, then `raffle_id`.
+const RAFFLE_SELLER_OFFSET: usize = 8 + 8;
+
+/// RPC connection plus the keypair that pays for and signs every
+/// transaction. Instructions that need a seller, buyer, admin or caller use
+/// this keypair for it.
+pub struct Client {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub format: Format,
+}
+
+impl Client {
+    pub fn new(url: String, payer: Keypair, format: Format) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            payer,
+            format,
+        }
+    }
+
+    fn send(&self, instructions: &[Instruction]) -> Result<String> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        let signature = self.rpc.send_and_confirm_transaction(&tx)?;
+        Ok(signature.to_string())
+    }
+
+    fn send_for(&self, raffle: &Pubkey, instruction: Instruction) -> Result<()> {
+        self.send_all_for(raffle, &[instruction])
+    }
+
+    fn send_all_for(&self, raffle: &Pubkey, instructions: &[Instruction]) -> Result<()> {
+        let signature = self.send(instructions)?;
+        print(
+            self.format,
+            &TxOutput {
+                signature: Some(signature),
+                raffle: Some(raffle.to_string()),
+            },
+        )
+    }
+
+    fn exists(&self, address: &Pubkey) -> Result<bool> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())?
+            .value
+            .is_some())
+    }
+
+    fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)
+            .with_context(|| format!("fetching {address}"))?;
+        decode_account(&data).with_context(|| format!("decoding {address}"))
+    }
+
+    fn raffle(&self, address: &Pubkey) -> Result<RaffleAccount> {
+        self.account(address)
+    }
+
+    fn token_account(&self, owner: &Pubkey, raffle: &RaffleAccount) -> Pubkey {
+        get_associated_token_address(owner, &raffle.payment_mint)
+    }
+
+    pub fn init(&self, params: ConfigParams) -> Result<()> {
+        let admin = self.payer.pubkey();
+        let mut ixs = Vec::new();
+        if !self.exists(&find_counter_address().0)? {
+            ixs.push(instructions::initialise_counter(&admin));
+        }
+        if !self.exists(&find_config_address().0)? {
+            ixs.push(instructions::initialise_config(&admin, params));
+        }
+        let signature = match ixs.is_empty() {
+            true => None,
+            false => Some(self.send(&ixs)?),
+        };
+        print(
+            self.format,
+            &TxOutput {
+                signature,
+                raffle: None,
+            },
+        )
+    }
+
+    pub fn add_mint(&self, mint: &Pubkey, treasury: &Pubkey) -> Result<()> {
+        self.send_config(instructions::add_payment_mint(
+            &self.payer.pubkey(),
+            mint,
+            treasury,
+        ))
+    }
+
+    /// Sends an admin instruction that touches no raffle.
+    fn send_config(&self, instruction: Instruction) -> Result<()> {
+        let signature = self.send(&[instruction])?;
+        print(
+            self.format,
+            &TxOutput {
+                signature: Some(signature),
+                raffle: None,
+            },
+        )
+    }
+
+    pub fn create(
+        &self,
+        item: ItemFile,
+        mint: &Pubkey,
+        token_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let seller = self.payer.pubkey();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let args = item.into_args(now)?;
+        let raffle_id = self.account::<Counter>(&find_counter_address().0)?.counter;
+        let token_account =
+            token_account.unwrap_or_else(|| get_associated_token_address(&seller, mint));
+
+        let ix = instructions::create_raffle(&seller, &token_account, mint, raffle_id, args);
+        let raffle = find_raffle_address(&seller, raffle_id).0;
+        self.send_for(&raffle, ix)
+    }
+
+    pub fn buy(&self, address: &Pubkey, tickets: u8, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let buyer = self.payer.pubkey();
+        let token_account = token_account.unwrap_or_else(|| self.token_account(&buyer, &raffle));
+        let ix = instructions::buy_tickets(&raffle, &buyer, &token_account, tickets);
+        self.send_for(address, ix)
+    }
+
+    pub fn show(&self, address: &Pubkey) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        print(self.format, &RaffleView::new(address, &raffle))
+    }
+
+    pub fn list(&self, seller: Option<Pubkey>, status: Option<RaffleStatus>) -> Result<()> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            RaffleAccount::DISCRIMINATOR.to_vec(),
+        ))];
+        if let Some(seller) = seller {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                RAFFLE_SELLER_OFFSET,
+                seller.to_bytes().to_vec(),
+            )));
+        }
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut raffles: Vec<(Pubkey, RaffleAccount)> = self
+            .rpc
+            .get_program_accounts_with_config(&ID, config)?
+            .into_iter()
+            .filter_map(|(address, account)| {
+                let raffle: RaffleAccount = decode_account(&account.data).ok()?;
+                Some((address, raffle))
+            })
+            .filter(|(_, raffle)| status.is_none_or(|status| raffle.status == status))
+            .collect();
+        raffles.sort_by_key(|(_, raffle)| raffle.raffle_id);
+
+        let rows: Vec<RaffleRow> = raffles
+            .iter()
+            .map(|(address, raffle)| RaffleRow::new(address, raffle))
+            .collect();
+        print(self.format, &rows)
+    }
+
+    pub fn close_sales(&self, address: &Pubkey) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        self.send_for(
+            address,
+            instructions::close_sales(&raffle, &self.payer.pubkey()),
+        )
+    }
+
+    pub fn abandon_draw(&self, address: &Pubkey) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        self.send_for(
+            address,
+            instructions::abandon_draw(&raffle, &self.payer.pubkey()),
+        )
+    }
+
+    pub fn request_draw(&self, address: &Pubkey, randomness: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        if raffle.randomness_source == RandomnessSource::Switchboard && randomness.is_none() {
+            bail!("Switchboard raffles need --randomness <randomness account>");
+        }
+        let ix = instructions::request_draw(&raffle, &self.payer.pubkey(), randomness);
+        self.send_for(address, ix)
+    }
+
+    pub fn draw(&self, address: &Pubkey, reveal_ix: Option<&Path>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let (randomness, reveal) = match raffle.randomness_source {
+            RandomnessSource::Switchboard => {
+                let path = reveal_ix.context("Switchboard draws need --reveal-ix <file>")?;
+                let reveal = reveal::load(path)?;
+                (self.revealed_value(&raffle, &reveal)?, Some(reveal))
+            }
+            _ => (expected_randomness(address, &raffle)?, None),
+        };
+        let ticket = RaffleAccount::pick_winning_ticket(&randomness, raffle.total_entries)
+            .map_err(|err| anyhow!("{err}"))?;
+        let page = self.page_holding(address, &raffle, ticket)?;
+        let randomness_account = reveal.as_ref().and(raffle.randomness_account);
+        let draw =
+            instructions::draw_winner(&raffle, &self.payer.pubkey(), randomness_account, page);
+        let ixs: Vec<Instruction> = reveal.into_iter().chain([draw]).collect();
+        self.send_all_for(address, &ixs)
+    }
+
+    /// Simulates the Switchboard reveal on its own to read the value it
+    /// writes, which is what `draw_winner` will see when both share a
+    /// transaction.
+    fn revealed_value(&self, raffle: &RaffleAccount, reveal: &Instruction) -> Result<[u8; 32]> {
+        let randomness_account = raffle
+            .randomness_account
+            .context("the draw has not been requested")?;
+        let tx =
+            Transaction::new_with_payer(std::slice::from_ref(reveal), Some(&self.payer.pubkey()));
+        let config = RpcSimulateTransactionConfig {
+            replace_recent_blockhash: true,
+            commitment: Some(self.rpc.commitment()),
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: vec![randomness_account.to_string()],
+            }),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(&tx, config)?
+            .value;
+        if let Some(err) = result.err {
+            bail!("the reveal failed in simulation: {err}");
+        }
+        let data = result
+            .accounts
+            .and_then(|accounts| accounts.into_iter().next().flatten())
+            .and_then(|account| account.data.decode())
+            .context("the simulation did not return the randomness account")?;
+        let randomness = RandomnessAccountData::try_deserialize(&mut data.as_slice())
+            .context("decoding the randomness account")?;
+        Ok(randomness.value)
+    }
+
+    pub fn rerequest_draw(&self, address: &Pubkey, randomness: &Pubkey) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let abandoned = raffle
+            .randomness_account
+            .context("the draw has not been requested")?;
+        let ix =
+            instructions::rerequest_draw(&raffle, &self.payer.pubkey(), &abandoned, randomness);
+        self.send_for(address, ix)
+    }
+
+    /// Index of the participant page that sold `ticket`.
+    fn page_holding(&self, address: &Pubkey, raffle: &RaffleAccount, ticket: u64) -> Result<u32> {
+        for page_index in 0..=raffle.current_page {
+            let page: ParticipantPage =
+                self.account(&find_participant_page_address(address, page_index).0)?;
+            if page.ticket_owner(ticket).is_some() {
+                return Ok(page_index);
+            }
+        }
+        bail!("no participant page holds ticket {ticket}")
+    }
+
+    pub fn refund(&self, address: &Pubkey, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let buyer = self.payer.pubkey();
+        let token_account = token_account.unwrap_or_else(|| self.token_account(&buyer, &raffle));
+        let ix = instructions::claim_refund(&raffle, &buyer, &token_account);
+        self.send_for(address, ix)
+    }
+
+    pub fn release(&self, address: &Pubkey, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let token_account =
+            token_account.unwrap_or_else(|| self.token_account(&raffle.seller, &raffle));
+        self.send_for(
+            address,
+            instructions::release_to_seller(&raffle, &token_account),
+        )
+    }
+
+    pub fn finalize(&self, address: &Pubkey, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let token_account =
+            token_account.unwrap_or_else(|| self.token_account(&raffle.seller, &raffle));
+        let ix = instructions::finalize_raffle(&raffle, &self.payer.pubkey(), &token_account);
+        self.send_for(address, ix)
+    }
+
+    pub fn cancel(&self, address: &Pubkey, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let token_account =
+            token_account.unwrap_or_else(|| self.token_account(&raffle.seller, &raffle));
+        self.send_for(
+            address,
+            instructions::cancel_raffle(&raffle, &token_account),
+        )
+    }
+
+    pub fn reclaim_bond(&self, address: &Pubkey, token_account: Option<Pubkey>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let token_account =
+            token_account.unwrap_or_else(|| self.token_account(&raffle.seller, &raffle));
+        self.send_for(address, instructions::reclaim_bond(&raffle, &token_account))
+    }
+
+    pub fn mark_shipped(&self, address: &Pubkey, tracking: Option<String>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        self.send_for(address, instructions::mark_shipped(&raffle, tracking))
+    }
+
+    pub fn mark_delivered(&self, address: &Pubkey, tracking: Option<String>) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let ix = instructions::mark_delivered(&raffle, &self.payer.pubkey(), tracking);
+        self.send_for(address, ix)
+    }
+
+    pub fn open_dispute(&self, address: &Pubkey, evidence: &Path) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let evidence =
+            fs::read(evidence).with_context(|| format!("reading {}", evidence.display()))?;
+        let evidence_hash = Sha256::digest(evidence).into();
+        let ix = instructions::open_dispute(&raffle, &self.payer.pubkey(), evidence_hash);
+        self.send_for(address, ix)
+    }
+
+    pub fn resolve_dispute(
+        &self,
+        address: &Pubkey,
+        resolution: DisputeResolution,
+        winner_token_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let winner_token_account = self.winner_token_account(&raffle, winner_token_account)?;
+        let ix = instructions::resolve_dispute(
+            &raffle,
+            &self.payer.pubkey(),
+            &winner_token_account,
+            resolution,
+        );
+        self.send_for(address, ix)
+    }
+
+    pub fn expire_unshipped(
+        &self,
+        address: &Pubkey,
+        winner_token_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let winner_token_account = self.winner_token_account(&raffle, winner_token_account)?;
+        let ix =
+            instructions::expire_unshipped(&raffle, &self.payer.pubkey(), &winner_token_account);
+        self.send_for(address, ix)
+    }
+
+    fn winner_token_account(
+        &self,
+        raffle: &RaffleAccount,
+        token_account: Option<Pubkey>,
+    ) -> Result<Pubkey> {
+        match token_account {
+            Some(token_account) => Ok(token_account),
+            None => {
+                let winner = raffle.winner.context("no winner has been drawn")?;
+                Ok(self.token_account(&winner, raffle))
+            }
+        }
+    }
+
+    pub fn pause(&self, flags: u16) -> Result<()> {
+        let ix = instructions::pause(&self.payer.pubkey(), flags);
+        self.send_config(ix)
+    }
+
+    pub fn unpause(&self, flags: u16) -> Result<()> {
+        let ix = instructions::unpause(&self.payer.pubkey(), flags);
+        self.send_config(ix)
+    }
+
+    pub fn close(
+        &self,
+        address: &Pubkey,
+        archive: bool,
+        token_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let raffle = self.raffle(address)?;
+        let token_account =
+            token_account.unwrap_or_else(|| self.token_account(&raffle.seller, &raffle));
+        let ix = instructions::close_raffle(&raffle, &token_account, archive);
+        self.send_for(address, ix)
+    }
+}
+
+/// The 32 bytes `draw_winner` will reveal for commit-reveal and
+/// deterministic raffles, recomputed off-chain so the transaction can name
+/// the participant page that holds the winning ticket. Switchboard values
+/// come from simulating the oracle's reveal instead.
+fn expected_randomness(address: &Pubkey, raffle: &RaffleAccount) -> Result<[u8; 32]> {
+    let commit_slot = raffle
+        .randomness_commit_slot
+        .context("the draw has not been requested")?;
+    let mut hasher = Sha256::new();
+    match raffle.randomness_source {
+        RandomnessSource::Switchboard => {
+            bail!("Switchboard values are read from the oracle reveal")
+        }
+        RandomnessSource::CommitReveal => {
+            hasher.update(raffle.entropy);
+            hasher.update(address.as_ref());
+        }
+        RandomnessSource::Deterministic => {
+            hasher.update(address.as_ref());
+            hasher.update(commit_slot.to_le_bytes());
+        }
+    }
+    Ok(hasher.finalize().into())
+}
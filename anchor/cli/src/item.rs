@@ -0,0 +1,142 @@
+//! Item files describing a raffle for `raffle create`, in JSON or TOML.
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use raffle_client::{instructions::CreateRaffleArgs, types::RandomnessSource};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Randomness {
+    #[default]
+    Switchboard,
+    CommitReveal,
+    /// Only for local validators running a program built with the
+    /// `test-randomness` feature; deployed programs reject it with
+    /// `RandomnessSourceUnavailable`.
+    Deterministic,
+}
+
+impl From<Randomness> for RandomnessSource {
+    fn from(randomness: Randomness) -> Self {
+        match randomness {
+            Randomness::Switchboard => RandomnessSource::Switchboard,
+            Randomness::CommitReveal => RandomnessSource::CommitReveal,
+            Randomness::Deterministic => RandomnessSource::Deterministic,
+        }
+    }
+}
+
+/// Prices are in whole tokens, as `create_raffle` takes them. The raffle ends
+/// at `deadline` (unix seconds) or `duration_seconds` from now; set exactly
+/// one of the two.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ItemFile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub image_uri: String,
+    pub selling_price: u64,
+    pub ticket_price: u64,
+    pub min_tickets: u32,
+    pub max_tickets: u32,
+    pub deadline: Option<i64>,
+    pub duration_seconds: Option<i64>,
+    #[serde(default)]
+    pub randomness: Randomness,
+}
+
+impl ItemFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        Self::parse(&text, extension.unwrap_or_default())
+            .with_context(|| format!("parsing {}", path.display()))
+    }
+
+    fn parse(text: &str, extension: &str) -> Result<Self> {
+        Ok(match extension {
+            "json" => serde_json::from_str(text)?,
+            "toml" => toml::from_str(text)?,
+            _ => bail!("item files must end in .json or .toml"),
+        })
+    }
+
+    pub fn into_args(self, now: i64) -> Result<CreateRaffleArgs> {
+        let deadline = match (self.deadline, self.duration_seconds) {
+            (Some(deadline), None) => deadline,
+            (None, Some(duration)) => now
+                .checked_add(duration)
+                .context("duration_seconds is too large")?,
+            _ => bail!("set exactly one of `deadline` and `duration_seconds`"),
+        };
+        Ok(CreateRaffleArgs {
+            item_name: self.name,
+            item_description: self.description,
+            item_image_uri: self.image_uri,
+            selling_price: self.selling_price,
+            ticket_price: self.ticket_price,
+            min_tickets: self.min_tickets,
+            max_tickets: self.max_tickets,
+            deadline,
+            randomness_source: self.randomness.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_and_toml_describe_the_same_item() {
+        let json = r#"{
+            "name": "Camera",
+            "description": "Film camera, boxed",
+            "selling_price": 100,
+            "ticket_price": 10,
+            "min_tickets": 5,
+            "max_tickets": 20,
+            "duration_seconds": 3600,
+            "randomness": "commit-reveal"
+        }"#;
+        let toml = r#"
+            name = "Camera"
+            description = "Film camera, boxed"
+            selling_price = 100
+            ticket_price = 10
+            min_tickets = 5
+            max_tickets = 20
+            duration_seconds = 3600
+            randomness = "commit-reveal"
+        "#;
+        let item = ItemFile::parse(json, "json").unwrap();
+        assert_eq!(item, ItemFile::parse(toml, "toml").unwrap());
+
+        let args = item.into_args(1_700_000_000).unwrap();
+        assert_eq!(args.deadline, 1_700_003_600);
+        assert_eq!(args.randomness_source, RandomnessSource::CommitReveal);
+        assert!(args.item_image_uri.is_empty());
+    }
+
+    #[test]
+    fn needs_exactly_one_deadline() {
+        let both = r#"
+            name = "Camera"
+            selling_price = 100
+            ticket_price = 10
+            min_tickets = 5
+            max_tickets = 20
+            deadline = 1700003600
+            duration_seconds = 3600
+        "#;
+        let item = ItemFile::parse(both, "toml").unwrap();
+        assert_eq!(item.randomness, Randomness::Switchboard);
+        assert!(item.into_args(0).is_err());
+        assert!(ItemFile::parse(both, "yaml").is_err());
+    }
+}
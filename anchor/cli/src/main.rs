@@ -0,0 +1,373 @@
+//! `raffle`: operate Raffle program deployments from the command line.
+//!
+//! Every command signs with one keypair, which acts as the admin, seller,
+//! buyer or crank caller the instruction needs. `--output json` prints
+//! machine-readable results for scripts.
+
+mod commands;
+mod item;
+mod output;
+mod reveal;
+
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use raffle_client::{
+    constants::{
+        PAUSE_ALL, PAUSE_BUY_TICKETS, PAUSE_CREATE_RAFFLE, PAUSE_DRAW_WINNER, PAUSE_PAYOUT,
+        PAUSE_REQUEST_DRAW,
+    },
+    types::{ConfigParams, DisputeResolution, RaffleStatus},
+};
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use commands::Client;
+use item::ItemFile;
+use output::Format;
+
+#[derive(Parser)]
+#[command(
+    name = "raffle",
+    version,
+    about = "Operate raffles on a Solana cluster"
+)]
+struct Cli {
+    /// JSON RPC endpoint
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        env = "RAFFLE_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Keypair that signs and pays [default: ~/.config/solana/id.json]
+    #[arg(long, short = 'k', global = true, env = "RAFFLE_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t = Format::Table)]
+    output: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the global counter and config if they do not exist yet. Only
+    /// the program's upgrade authority can create the config
+    Init {
+        /// Settles disputes [default: the signer]
+        #[arg(long)]
+        arbiter: Option<Pubkey>,
+        #[arg(long, default_value_t = 0)]
+        fee_bps: u16,
+        #[arg(long, default_value_t = 0)]
+        bond_bps: u16,
+        #[arg(long, default_value_t = 3 * 24 * 60 * 60)]
+        dispute_window_seconds: i64,
+        #[arg(long, default_value_t = 14 * 24 * 60 * 60)]
+        shipping_window_seconds: i64,
+        #[arg(long, default_value_t = 1_500)]
+        randomness_timeout_slots: u64,
+    },
+    /// Allow raffles priced in MINT, with fees paid to TREASURY
+    AddMint { mint: Pubkey, treasury: Pubkey },
+    /// Create a raffle from a JSON or TOML item file
+    Create {
+        item: PathBuf,
+        #[arg(long)]
+        mint: Pubkey,
+        /// Pays the seller bond [default: the signer's associated account]
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Buy tickets in a raffle
+    Buy {
+        raffle: Pubkey,
+        #[arg(long, short = 'n')]
+        tickets: u8,
+        /// Pays for the tickets [default: the signer's associated account]
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Print a raffle's full decoded state
+    Show { raffle: Pubkey },
+    /// List raffles, optionally by seller and status
+    List {
+        #[arg(long)]
+        seller: Option<Pubkey>,
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+    },
+    /// End ticket sales after the deadline
+    CloseSales { raffle: Pubkey },
+    /// Commit to the randomness the draw will use
+    RequestDraw {
+        raffle: Pubkey,
+        /// Switchboard randomness account, for Switchboard raffles
+        #[arg(long)]
+        randomness: Option<Pubkey>,
+    },
+    /// Draw the winner. Switchboard raffles are drawn in the same transaction
+    /// as the oracle's reveal, so they need --reveal-ix
+    Draw {
+        raffle: Pubkey,
+        /// JSON file with the Switchboard reveal instruction, paid by the signer
+        #[arg(long)]
+        reveal_ix: Option<PathBuf>,
+    },
+    /// Replace a Switchboard commitment that timed out (signed by the admin)
+    RerequestDraw {
+        raffle: Pubkey,
+        /// Fresh Switchboard randomness account, committed in the previous slot
+        #[arg(long)]
+        randomness: Pubkey,
+    },
+    /// Send a commit-reveal raffle nobody revealed entropy for to refunds
+    AbandonDraw { raffle: Pubkey },
+    /// Claim the signer's refund from a raffle that missed its minimum
+    Refund {
+        raffle: Pubkey,
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Pay the seller once delivery is confirmed (signed by the seller)
+    Release {
+        raffle: Pubkey,
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Pay the seller if needed and close the escrow
+    Finalize {
+        raffle: Pubkey,
+        /// Seller's token account [default: the seller's associated account]
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Withdraw a raffle nobody bought into and get the bond back (signed by
+    /// the seller)
+    Cancel {
+        raffle: Pubkey,
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Return the seller bond of a raffle that failed through nobody's fault
+    ReclaimBond {
+        raffle: Pubkey,
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+    /// Mark the prize shipped (signed by the seller)
+    MarkShipped {
+        raffle: Pubkey,
+        #[arg(long)]
+        tracking: Option<String>,
+    },
+    /// Confirm the prize arrived (signed by the winner)
+    MarkDelivered {
+        raffle: Pubkey,
+        #[arg(long)]
+        tracking: Option<String>,
+    },
+    /// Contest a shipment (signed by the winner). Only the SHA-256 of the
+    /// evidence file goes on-chain
+    OpenDispute {
+        raffle: Pubkey,
+        #[arg(long)]
+        evidence: PathBuf,
+    },
+    /// Settle an open dispute (signed by the arbiter)
+    ResolveDispute {
+        raffle: Pubkey,
+        #[arg(value_enum)]
+        resolution: Resolution,
+        /// Receives a slashed bond [default: the winner's associated account]
+        #[arg(long)]
+        winner_token_account: Option<Pubkey>,
+    },
+    /// Send a raffle whose seller missed the shipping deadline to refunds and
+    /// slash the bond to the winner
+    ExpireUnshipped {
+        raffle: Pubkey,
+        /// [default: the winner's associated account]
+        #[arg(long)]
+        winner_token_account: Option<Pubkey>,
+    },
+    /// Pause instructions program-wide (signed by the admin)
+    Pause {
+        #[arg(value_enum, required = true)]
+        flags: Vec<PauseFlag>,
+    },
+    /// Lift pauses set with `pause` (signed by the admin)
+    Unpause {
+        #[arg(value_enum, required = true)]
+        flags: Vec<PauseFlag>,
+    },
+    /// Close a settled raffle, returning its rent to the seller
+    Close {
+        raffle: Pubkey,
+        /// Keep a RaffleArchive summary
+        #[arg(long)]
+        archive: bool,
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Status {
+    Active,
+    Drawing,
+    Completed,
+    Cancelled,
+    Refunding,
+    Refunded,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Resolution {
+    ReleaseToSeller,
+    RefundParticipants,
+}
+
+impl From<Resolution> for DisputeResolution {
+    fn from(resolution: Resolution) -> Self {
+        match resolution {
+            Resolution::ReleaseToSeller => DisputeResolution::ReleaseToSeller,
+            Resolution::RefundParticipants => DisputeResolution::RefundParticipants,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PauseFlag {
+    CreateRaffle,
+    BuyTickets,
+    RequestDraw,
+    DrawWinner,
+    /// Payouts to the seller; refunds always stay open
+    Payout,
+    All,
+}
+
+impl PauseFlag {
+    fn bits(flags: &[PauseFlag]) -> u16 {
+        flags.iter().fold(0, |bits, flag| {
+            bits | match flag {
+                PauseFlag::CreateRaffle => PAUSE_CREATE_RAFFLE,
+                PauseFlag::BuyTickets => PAUSE_BUY_TICKETS,
+                PauseFlag::RequestDraw => PAUSE_REQUEST_DRAW,
+                PauseFlag::DrawWinner => PAUSE_DRAW_WINNER,
+                PauseFlag::Payout => PAUSE_PAYOUT,
+                PauseFlag::All => PAUSE_ALL,
+            }
+        })
+    }
+}
+
+impl From<Status> for RaffleStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Active => RaffleStatus::Active,
+            Status::Drawing => RaffleStatus::Drawing,
+            Status::Completed => RaffleStatus::Completed,
+            Status::Cancelled => RaffleStatus::Cancelled,
+            Status::Refunding => RaffleStatus::Refunding,
+            Status::Refunded => RaffleStatus::Refunded,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME").context("HOME is not set")?)
+            .join(".config/solana/id.json"),
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow::anyhow!("reading {}: {err}", keypair_path.display()))?;
+    let client = Client::new(cli.url, payer, cli.output);
+
+    match cli.command {
+        Command::Init {
+            arbiter,
+            fee_bps,
+            bond_bps,
+            dispute_window_seconds,
+            shipping_window_seconds,
+            randomness_timeout_slots,
+        } => client.init(ConfigParams {
+            arbiter: arbiter.unwrap_or_else(|| client.payer.pubkey()),
+            fee_bps,
+            bond_bps,
+            dispute_window_seconds,
+            shipping_window_seconds,
+            randomness_timeout_slots,
+        }),
+        Command::AddMint { mint, treasury } => client.add_mint(&mint, &treasury),
+        Command::Create {
+            item,
+            mint,
+            token_account,
+        } => client.create(ItemFile::load(&item)?, &mint, token_account),
+        Command::Buy {
+            raffle,
+            tickets,
+            token_account,
+        } => client.buy(&raffle, tickets, token_account),
+        Command::Show { raffle } => client.show(&raffle),
+        Command::List { seller, status } => client.list(seller, status.map(Into::into)),
+        Command::CloseSales { raffle } => client.close_sales(&raffle),
+        Command::RequestDraw { raffle, randomness } => client.request_draw(&raffle, randomness),
+        Command::Draw { raffle, reveal_ix } => client.draw(&raffle, reveal_ix.as_deref()),
+        Command::RerequestDraw { raffle, randomness } => {
+            client.rerequest_draw(&raffle, &randomness)
+        }
+        Command::AbandonDraw { raffle } => client.abandon_draw(&raffle),
+        Command::Refund {
+            raffle,
+            token_account,
+        } => client.refund(&raffle, token_account),
+        Command::Release {
+            raffle,
+            token_account,
+        } => client.release(&raffle, token_account),
+        Command::Finalize {
+            raffle,
+            token_account,
+        } => client.finalize(&raffle, token_account),
+        Command::Cancel {
+            raffle,
+            token_account,
+        } => client.cancel(&raffle, token_account),
+        Command::ReclaimBond {
+            raffle,
+            token_account,
+        } => client.reclaim_bond(&raffle, token_account),
+        Command::MarkShipped { raffle, tracking } => client.mark_shipped(&raffle, tracking),
+        Command::MarkDelivered { raffle, tracking } => client.mark_delivered(&raffle, tracking),
+        Command::OpenDispute { raffle, evidence } => client.open_dispute(&raffle, &evidence),
+        Command::ResolveDispute {
+            raffle,
+            resolution,
+            winner_token_account,
+        } => client.resolve_dispute(&raffle, resolution.into(), winner_token_account),
+        Command::ExpireUnshipped {
+            raffle,
+            winner_token_account,
+        } => client.expire_unshipped(&raffle, winner_token_account),
+        Command::Pause { flags } => client.pause(PauseFlag::bits(&flags)),
+        Command::Unpause { flags } => client.unpause(PauseFlag::bits(&flags)),
+        Command::Close {
+            raffle,
+            archive,
+            token_account,
+        } => client.close(&raffle, archive, token_account),
+    }
+}
@@ -0,0 +1,237 @@
+//! Everything the CLI prints goes through `print`, as a human table or as
+//! JSON for scripts.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::ValueEnum;
+use raffle_client::types::RaffleAccount;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+}
+
+pub fn print<T: Serialize>(format: Format, value: &T) -> Result<()> {
+    let value = serde_json::to_value(value)?;
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&value)?),
+        Format::Table => print!("{}", render_table(&value)),
+    }
+    Ok(())
+}
+
+/// A list of objects becomes one row per object under a header row; a
+/// single object becomes one `field  value` row per field.
+fn render_table(value: &Value) -> String {
+    let rows: Vec<Vec<String>> = match value {
+        Value::Array(items) => {
+            let Some(Value::Object(first)) = items.first() else {
+                return "(none)\n".to_string();
+            };
+            let header = first.keys().map(|key| key.to_uppercase()).collect();
+            std::iter::once(header)
+                .chain(
+                    items
+                        .iter()
+                        .map(|item| first.keys().map(|key| cell(&item[key])).collect()),
+                )
+                .collect()
+        }
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| vec![key.clone(), cell(value)])
+            .collect(),
+        other => vec![vec![cell(other)]],
+    };
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Result of a command that sends a transaction. `signature` is `None` when
+/// there was nothing to do.
+#[derive(Serialize)]
+pub struct TxOutput {
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raffle: Option<String>,
+}
+
+/// One line of `raffle list`.
+#[derive(Serialize)]
+pub struct RaffleRow {
+    pub address: String,
+    pub id: u64,
+    pub status: String,
+    pub item: String,
+    pub entries: String,
+    pub deadline: i64,
+    pub seller: String,
+}
+
+impl RaffleRow {
+    pub fn new(address: &Pubkey, raffle: &RaffleAccount) -> Self {
+        Self {
+            address: address.to_string(),
+            id: raffle.raffle_id,
+            status: format!("{:?}", raffle.status),
+            item: raffle.item_name.clone(),
+            entries: format!(
+                "{}/{} (max {})",
+                raffle.total_entries, raffle.min_tickets, raffle.max_tickets
+            ),
+            deadline: raffle.deadline,
+            seller: raffle.seller.to_string(),
+        }
+    }
+}
+
+/// Full decoded state for `raffle show`. Amounts are in the mint's base
+/// units, times in unix seconds.
+#[derive(Serialize)]
+pub struct RaffleView {
+    pub address: String,
+    pub raffle_id: u64,
+    pub seller: String,
+    pub status: String,
+    pub item_name: String,
+    pub item_description: String,
+    pub item_image_uri: String,
+    pub payment_mint: String,
+    pub selling_price: u64,
+    pub ticket_price: u64,
+    pub min_tickets: u32,
+    pub max_tickets: u32,
+    pub deadline: i64,
+    pub total_entries: u64,
+    pub participant_count: u32,
+    pub participant_pages: u32,
+    pub is_sold_out: bool,
+    pub total_collected: u64,
+    pub total_refunded: u64,
+    pub randomness_source: String,
+    pub randomness_account: Option<String>,
+    pub randomness_commit_slot: Option<u64>,
+    pub entropy_commitments: u32,
+    pub entropy_reveals: u32,
+    pub winner: Option<String>,
+    pub winning_ticket: Option<u64>,
+    pub delivery_status: String,
+    pub tracking_info: Option<String>,
+    pub shipping_deadline: Option<i64>,
+    pub shipped_at: Option<i64>,
+    pub dispute_deadline: Option<i64>,
+    pub disputed_at: Option<i64>,
+    pub dispute_resolution: Option<String>,
+    pub payout_released: bool,
+    pub fee_bps: u16,
+    pub fee_collected: u64,
+    pub treasury: String,
+    pub bond_amount: u64,
+    pub bond_status: String,
+}
+
+impl RaffleView {
+    pub fn new(address: &Pubkey, raffle: &RaffleAccount) -> Self {
+        Self {
+            address: address.to_string(),
+            raffle_id: raffle.raffle_id,
+            seller: raffle.seller.to_string(),
+            status: format!("{:?}", raffle.status),
+            item_name: raffle.item_name.clone(),
+            item_description: raffle.item_description.clone(),
+            item_image_uri: raffle.item_image_uri.clone(),
+            payment_mint: raffle.payment_mint.to_string(),
+            selling_price: raffle.selling_price,
+            ticket_price: raffle.ticket_price,
+            min_tickets: raffle.min_tickets,
+            max_tickets: raffle.max_tickets,
+            deadline: raffle.deadline,
+            total_entries: raffle.total_entries,
+            participant_count: raffle.participant_count,
+            participant_pages: raffle.current_page + 1,
+            is_sold_out: raffle.is_sold_out,
+            total_collected: raffle.total_collected,
+            total_refunded: raffle.total_refunded,
+            randomness_source: format!("{:?}", raffle.randomness_source),
+            randomness_account: raffle.randomness_account.map(|key| key.to_string()),
+            randomness_commit_slot: raffle.randomness_commit_slot,
+            entropy_commitments: raffle.entropy_commitments,
+            entropy_reveals: raffle.entropy_reveals,
+            winner: raffle.winner.map(|key| key.to_string()),
+            winning_ticket: raffle.winning_ticket,
+            delivery_status: format!("{:?}", raffle.product_delivered_status),
+            tracking_info: raffle.tracking_info.clone(),
+            shipping_deadline: raffle.shipping_deadline,
+            shipped_at: raffle.shipped_at,
+            dispute_deadline: raffle.despute_deadline,
+            disputed_at: raffle.disputed_at,
+            dispute_resolution: raffle
+                .dispute_resolution
+                .map(|resolution| format!("{resolution:?}")),
+            payout_released: raffle.payout_released,
+            fee_bps: raffle.fee_bps,
+            fee_collected: raffle.fee_collected,
+            treasury: raffle.treasury.to_string(),
+            bond_amount: raffle.bond_amount,
+            bond_status: format!("{:?}", raffle.bond_status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_lists_and_objects() {
+        let list = json!([
+            { "id": 0, "status": "Active", "winner": null },
+            { "id": 12, "status": "Completed", "winner": "7xKX" },
+        ]);
+        assert_eq!(
+            render_table(&list),
+            "ID  STATUS     WINNER\n0   Active     -\n12  Completed  7xKX\n"
+        );
+
+        let object = json!({ "raffle_id": 3, "payout_released": false });
+        assert_eq!(
+            render_table(&object),
+            "raffle_id        3\npayout_released  false\n"
+        );
+        assert_eq!(render_table(&json!([])), "(none)\n");
+    }
+}
@@ -0,0 +1,89 @@
+//! Switchboard reveal instructions for `raffle draw`, as JSON files.
+//!
+//! The oracle signs each reveal, so the instruction has to come from
+//! Switchboard's SDK, e.g. `await randomness.revealIx(payer)` in
+//! `@switchboard-xyz/on-demand`, saved with the signer of this tool as payer:
+//!
+//! ```json
+//! {
+//!   "programId": "SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv",
+//!   "keys": [{ "pubkey": "...", "isSigner": false, "isWritable": true }],
+//!   "data": "<base64>"
+//! }
+//! ```
+
+use std::{fs, path::Path, str::FromStr};
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct InstructionFile {
+    program_id: String,
+    keys: Vec<KeyFile>,
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct KeyFile {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+pub fn load(path: &Path) -> Result<Instruction> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    parse(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn parse(text: &str) -> Result<Instruction> {
+    let file: InstructionFile = serde_json::from_str(text)?;
+    let accounts = file
+        .keys
+        .iter()
+        .map(|key| {
+            let pubkey = Pubkey::from_str(&key.pubkey)
+                .with_context(|| format!("invalid account {}", key.pubkey))?;
+            Ok(AccountMeta {
+                pubkey,
+                is_signer: key.is_signer,
+                is_writable: key.is_writable,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Instruction {
+        program_id: Pubkey::from_str(&file.program_id).context("invalid programId")?,
+        accounts,
+        data: STANDARD.decode(&file.data).context("data is not base64")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_web3_style_instruction() {
+        let program = Pubkey::new_unique();
+        let randomness = Pubkey::new_unique();
+        let text = format!(
+            r#"{{
+                "programId": "{program}",
+                "keys": [{{ "pubkey": "{randomness}", "isSigner": false, "isWritable": true }}],
+                "data": "AQID"
+            }}"#
+        );
+
+        let ix = parse(&text).unwrap();
+        assert_eq!(ix.program_id, program);
+        assert_eq!(ix.accounts, vec![AccountMeta::new(randomness, false)]);
+        assert_eq!(ix.data, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use Raffle::{
+    events::*,
+    types::{Counter, RaffleAccount},
+};
+
+/// Decodes any of the program's accounts, checking its discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_raffle(data: &[u8]) -> Result<RaffleAccount> {
+    decode_account(data)
+}
+
+pub fn decode_counter(data: &[u8]) -> Result<Counter> {
+    decode_account(data)
+}
+
+macro_rules! raffle_events {
+    ($($event:ident),* $(,)?) => {
+        /// Every event the program emits.
+        #[derive(Debug)]
+        pub enum RaffleEvent {
+            $($event($event),)*
+        }
+
+        impl RaffleEvent {
+            /// Decodes the payload of one `Program data:` log line, i.e. the
+            /// event discriminator followed by the borsh encoded event.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(body) = data.strip_prefix($event::DISCRIMINATOR) {
+                        return $event::try_from_slice(body).ok().map(Self::$event);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+raffle_events!(
+    RaffleCreated,
+    TicketsBought,
+    ProductShipped,
+    ProductDelivered,
+    RefundClaimed,
+    SellerPaid,
+    RaffleCancelled,
+    DisputeOpened,
+    DisputeResolved,
+    RandomnessRerequested,
+    EntropyCommitted,
+    EntropyRevealed,
+    DrawAbandoned,
+    WinnerDrawn,
+    ProgramPaused,
+    ProgramUnpaused,
+    BondReturned,
+    BondSlashed,
+    ShippingExpired,
+    RaffleSettled,
+    RaffleClosed,
+    SalesClosed,
+);
+
+/// Events in a transaction's log messages, in emission order. Lines that are
+/// not Raffle events are skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<RaffleEvent> {
+    logs.iter()
+        .filter_map(|line| line.as_ref().strip_prefix("Program data: "))
+        .filter_map(|payload| STANDARD.decode(payload).ok())
+        .filter_map(|data| RaffleEvent::decode(&data))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{prelude::Pubkey, Event};
+
+    #[test]
+    fn parses_events_from_logs() {
+        let raffle = Pubkey::new_unique();
+        let created = RaffleCreated {
+            raffle,
+            seller: Pubkey::new_unique(),
+            ticket_price: 10_000_000,
+            deadline: 1_700_000_000,
+            bond_amount: 0,
+        };
+        let closed = SalesClosed {
+            raffle,
+            total_entries: 12,
+            min_tickets: 10,
+            status: Raffle::types::RaffleStatus::Drawing,
+            closed_at: 1_700_000_001,
+        };
+        let logs = vec![
+            "Program F1o3uGF7BP9tTvNXEPLFHtynamJfwpFkBAwsds1nEx8p invoke [1]".to_string(),
+            format!("Program data: {}", STANDARD.encode(created.data())),
+            "Program log: Instruction: CloseSales".to_string(),
+            format!("Program data: {}", STANDARD.encode(closed.data())),
+            format!("Program data: {}", STANDARD.encode([0u8; 16])),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            RaffleEvent::RaffleCreated(event) => {
+                assert_eq!(event.raffle, raffle);
+                assert_eq!(event.ticket_price, 10_000_000);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+        match &events[1] {
+            RaffleEvent::SalesClosed(event) => assert_eq!(event.total_entries, 12),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,474 @@
+//! One builder per program instruction. Builders that act on an existing
+//! raffle take its decoded `RaffleAccount` and derive the raffle, escrow and
+//! page addresses from it.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{associated_token, token};
+
+use Raffle::{
+    accounts, instruction,
+    pda::{
+        find_config_address, find_counter_address, find_draw_record_address, find_escrow_address,
+        find_participant_page_address, find_program_data_address, find_raffle_address,
+        find_raffle_archive_address, find_ticket_receipt_address,
+    },
+    types::{ConfigParams, DisputeResolution, RaffleAccount, RandomnessSource},
+};
+
+/// Arguments of `create_raffle`. Prices are in whole tokens; the program
+/// scales them by the payment mint's decimals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateRaffleArgs {
+    pub item_name: String,
+    pub item_description: String,
+    pub item_image_uri: String,
+    pub selling_price: u64,
+    pub ticket_price: u64,
+    pub min_tickets: u32,
+    pub max_tickets: u32,
+    pub deadline: i64,
+    pub randomness_source: RandomnessSource,
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: Raffle::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Address of a raffle from its decoded state.
+pub fn raffle_address(raffle: &RaffleAccount) -> Pubkey {
+    find_raffle_address(&raffle.seller, raffle.raffle_id).0
+}
+
+/// Address of a raffle's escrow token account from its decoded state.
+pub fn escrow_address(raffle: &RaffleAccount) -> Pubkey {
+    find_escrow_address(&raffle.seller, raffle.raffle_id).0
+}
+
+pub fn initialise_counter(signer: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeCounter {
+            counter: find_counter_address().0,
+            signer: *signer,
+            system_program: system_program::ID,
+        },
+        instruction::InitialiseCounter {},
+    )
+}
+
+/// `admin` must be the program's upgrade authority.
+pub fn initialise_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: find_config_address().0,
+            admin: *admin,
+            program: Raffle::ID,
+            program_data: find_program_data_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitialiseConfig { params },
+    )
+}
+
+fn update_config_accounts(admin: &Pubkey) -> accounts::UpdateConfig {
+    accounts::UpdateConfig {
+        config: find_config_address().0,
+        admin: *admin,
+    }
+}
+
+pub fn update_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    build(
+        update_config_accounts(admin),
+        instruction::UpdateConfig { params },
+    )
+}
+
+pub fn set_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        update_config_accounts(admin),
+        instruction::SetAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn add_payment_mint(admin: &Pubkey, payment_mint: &Pubkey, treasury: &Pubkey) -> Instruction {
+    build(
+        accounts::AddPaymentMint {
+            config: find_config_address().0,
+            admin: *admin,
+            payment_mint: *payment_mint,
+            treasury: *treasury,
+        },
+        instruction::AddPaymentMint {},
+    )
+}
+
+pub fn remove_payment_mint(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        update_config_accounts(admin),
+        instruction::RemovePaymentMint { mint: *mint },
+    )
+}
+
+pub fn pause(admin: &Pubkey, flags: u16) -> Instruction {
+    build(update_config_accounts(admin), instruction::Pause { flags })
+}
+
+pub fn unpause(admin: &Pubkey, flags: u16) -> Instruction {
+    build(
+        update_config_accounts(admin),
+        instruction::Unpause { flags },
+    )
+}
+
+/// `raffle_id` must be the current value of the global counter.
+pub fn create_raffle(
+    seller: &Pubkey,
+    seller_token_account: &Pubkey,
+    payment_mint: &Pubkey,
+    raffle_id: u64,
+    args: CreateRaffleArgs,
+) -> Instruction {
+    build(
+        accounts::CreateRaffle {
+            seller: *seller,
+            counter: find_counter_address().0,
+            config: find_config_address().0,
+            payment_mint: *payment_mint,
+            seller_token_account: *seller_token_account,
+            raffle_account: find_raffle_address(seller, raffle_id).0,
+            escrow_payment_account: find_escrow_address(seller, raffle_id).0,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateRaffle {
+            item_name: args.item_name,
+            item_description: args.item_description,
+            item_image_uri: args.item_image_uri,
+            selling_price: args.selling_price,
+            ticket_price: args.ticket_price,
+            min_tickets: args.min_tickets,
+            max_tickets: args.max_tickets,
+            deadline: args.deadline,
+            randomness_source: args.randomness_source,
+        },
+    )
+}
+
+pub fn buy_tickets(
+    raffle: &RaffleAccount,
+    buyer: &Pubkey,
+    buyer_token_account: &Pubkey,
+    num_tickets: u8,
+) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::BuyTickets {
+            buyer: *buyer,
+            buyer_token_accont: *buyer_token_account,
+            raffle_account: raffle_key,
+            config: find_config_address().0,
+            escrow_payment_account: escrow_address(raffle),
+            ticket_receipt: find_ticket_receipt_address(&raffle_key, buyer).0,
+            participant_page: find_participant_page_address(&raffle_key, raffle.current_page).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::BuyTickets { num_tickets },
+    )
+}
+
+pub fn close_sales(raffle: &RaffleAccount, caller: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseSales {
+            raffle_account: raffle_address(raffle),
+            caller: *caller,
+        },
+        instruction::CloseSales {},
+    )
+}
+
+pub fn abandon_draw(raffle: &RaffleAccount, caller: &Pubkey) -> Instruction {
+    build(
+        accounts::AbandonDraw {
+            raffle_account: raffle_address(raffle),
+            caller: *caller,
+        },
+        instruction::AbandonDraw {},
+    )
+}
+
+/// `randomness_account` is the Switchboard randomness account; leave it out
+/// for the other randomness sources.
+pub fn request_draw(
+    raffle: &RaffleAccount,
+    authority: &Pubkey,
+    randomness_account: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::RequestDraw {
+            raffle_account: raffle_address(raffle),
+            config: find_config_address().0,
+            randomness_account_data: randomness_account,
+            authority: *authority,
+        },
+        instruction::RequestDraw {},
+    )
+}
+
+/// `admin` must be the config admin.
+pub fn rerequest_draw(
+    raffle: &RaffleAccount,
+    admin: &Pubkey,
+    abandoned_randomness_account: &Pubkey,
+    randomness_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RerequestDraw {
+            raffle_account: raffle_address(raffle),
+            config: find_config_address().0,
+            abandoned_randomness_account_data: *abandoned_randomness_account,
+            randomness_account_data: *randomness_account,
+            authority: *admin,
+        },
+        instruction::RerequestDraw {},
+    )
+}
+
+/// `participant_page` is the index of the page holding the winning ticket;
+/// see `RaffleAccount::pick_winning_ticket` and `ParticipantPage::ticket_owner`.
+pub fn draw_winner(
+    raffle: &RaffleAccount,
+    payer: &Pubkey,
+    randomness_account: Option<Pubkey>,
+    participant_page: u32,
+) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::DrawWinner {
+            raffle_account: raffle_key,
+            config: find_config_address().0,
+            randomness_account_data: randomness_account,
+            participant_page: find_participant_page_address(&raffle_key, participant_page).0,
+            draw_record: find_draw_record_address(&raffle_key).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::DrawWinner {},
+    )
+}
+
+/// `commitment` is `randomness::entropy_commitment(raffle, buyer, secret)`.
+pub fn commit_entropy(raffle: &RaffleAccount, buyer: &Pubkey, commitment: [u8; 32]) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::CommitEntropy {
+            buyer: *buyer,
+            raffle_account: raffle_key,
+            ticket_receipt: find_ticket_receipt_address(&raffle_key, buyer).0,
+        },
+        instruction::CommitEntropy { commitment },
+    )
+}
+
+pub fn reveal_entropy(raffle: &RaffleAccount, buyer: &Pubkey, secret: [u8; 32]) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::RevealEntropy {
+            buyer: *buyer,
+            raffle_account: raffle_key,
+            ticket_receipt: find_ticket_receipt_address(&raffle_key, buyer).0,
+        },
+        instruction::RevealEntropy { secret },
+    )
+}
+
+pub fn claim_refund(
+    raffle: &RaffleAccount,
+    buyer: &Pubkey,
+    buyer_token_account: &Pubkey,
+) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::ClaimRefund {
+            buyer: *buyer,
+            buyer_token_account: *buyer_token_account,
+            raffle_account: raffle_key,
+            escrow_payment_account: escrow_address(raffle),
+            ticket_receipt: find_ticket_receipt_address(&raffle_key, buyer).0,
+            token_program: token::ID,
+        },
+        instruction::ClaimRefund {},
+    )
+}
+
+pub fn reclaim_bond(raffle: &RaffleAccount, seller_token_account: &Pubkey) -> Instruction {
+    build(
+        accounts::ReclaimBond {
+            seller: raffle.seller,
+            seller_token_account: *seller_token_account,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            config: find_config_address().0,
+            token_program: token::ID,
+        },
+        instruction::ReclaimBond {},
+    )
+}
+
+pub fn release_to_seller(raffle: &RaffleAccount, seller_token_account: &Pubkey) -> Instruction {
+    build(
+        accounts::ReleaseToSeller {
+            seller: raffle.seller,
+            seller_token_account: *seller_token_account,
+            treasury: raffle.treasury,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            config: find_config_address().0,
+            token_program: token::ID,
+        },
+        instruction::ReleaseToSeller {},
+    )
+}
+
+pub fn finalize_raffle(
+    raffle: &RaffleAccount,
+    caller: &Pubkey,
+    seller_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FinalizeRaffle {
+            caller: *caller,
+            seller: raffle.seller,
+            seller_token_account: *seller_token_account,
+            treasury: raffle.treasury,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            config: find_config_address().0,
+            token_program: token::ID,
+        },
+        instruction::FinalizeRaffle {},
+    )
+}
+
+pub fn cancel_raffle(raffle: &RaffleAccount, seller_token_account: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelRaffle {
+            seller: raffle.seller,
+            seller_token_account: *seller_token_account,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            token_program: token::ID,
+        },
+        instruction::CancelRaffle {},
+    )
+}
+
+/// The escrow is always passed, whether or not `finalize_raffle` has closed
+/// it; `archive` writes a `RaffleArchive` summary.
+pub fn close_raffle(
+    raffle: &RaffleAccount,
+    seller_token_account: &Pubkey,
+    archive: bool,
+) -> Instruction {
+    let raffle_key = raffle_address(raffle);
+    build(
+        accounts::CloseRaffle {
+            seller: raffle.seller,
+            seller_token_account: *seller_token_account,
+            raffle_account: raffle_key,
+            escrow_payment_account: escrow_address(raffle),
+            raffle_archive: archive.then(|| find_raffle_archive_address(&raffle_key).0),
+            config: find_config_address().0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CloseRaffle {},
+    )
+}
+
+pub fn open_dispute(
+    raffle: &RaffleAccount,
+    winner: &Pubkey,
+    evidence_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::OpenDispute {
+            winner: *winner,
+            raffle_account: raffle_address(raffle),
+        },
+        instruction::OpenDispute { evidence_hash },
+    )
+}
+
+pub fn resolve_dispute(
+    raffle: &RaffleAccount,
+    arbiter: &Pubkey,
+    winner_token_account: &Pubkey,
+    resolution: DisputeResolution,
+) -> Instruction {
+    build(
+        accounts::ResolveDispute {
+            arbiter: *arbiter,
+            config: find_config_address().0,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            winner_token_account: *winner_token_account,
+            token_program: token::ID,
+        },
+        instruction::ResolveDispute { resolution },
+    )
+}
+
+pub fn expire_unshipped(
+    raffle: &RaffleAccount,
+    caller: &Pubkey,
+    winner_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExpireUnshipped {
+            caller: *caller,
+            raffle_account: raffle_address(raffle),
+            escrow_payment_account: escrow_address(raffle),
+            winner_token_account: *winner_token_account,
+            token_program: token::ID,
+        },
+        instruction::ExpireUnshipped {},
+    )
+}
+
+pub fn mark_shipped(raffle: &RaffleAccount, tracking_info: Option<String>) -> Instruction {
+    build(
+        accounts::MarkShipped {
+            seller: raffle.seller,
+            config: find_config_address().0,
+            raffle_account: raffle_address(raffle),
+        },
+        instruction::MarkShipped { tracking_info },
+    )
+}
+
+pub fn mark_delivered(
+    raffle: &RaffleAccount,
+    winner: &Pubkey,
+    tracking_info: Option<String>,
+) -> Instruction {
+    build(
+        accounts::MarkDelivered {
+            winner: *winner,
+            raffle_account: raffle_address(raffle),
+        },
+        instruction::MarkDelivered { tracking_info },
+    )
+}
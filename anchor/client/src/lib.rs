@@ -0,0 +1,12 @@
+//! Rust client for the Raffle program: typed instruction builders, PDA
+//! derivation and decoders for accounts and events.
+//!
+//! The program crate is linked with `no-entrypoint`, so account and event
+//! types are the program's own and can never drift from it.
+
+pub mod decode;
+pub mod instructions;
+
+pub use Raffle::{constants, error, events, pda, randomness, types, ID};
+
+pub use decode::{decode_account, decode_counter, decode_raffle, parse_logs, RaffleEvent};
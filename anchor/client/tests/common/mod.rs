@@ -0,0 +1,327 @@
+//! Shared harness for the SVM tests: starts the program with a config, a
+//! 6-decimal payment mint and its treasury, and offers helpers to fund users,
+//! warp the clock and read decoded state.
+//!
+//! The SVM deploys the SBF build of the program (`target/deploy/Raffle.so`),
+//! because Anchor's CPIs only work inside the real runtime. Run `anchor build`
+//! and then `cargo test -p raffle-client -- --ignored`.
+
+#![allow(dead_code)]
+
+pub mod randomness;
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::{instruction::Instruction, program_pack::Pack, system_instruction},
+    AccountDeserialize,
+};
+use anchor_spl::token::spl_token;
+use raffle_client::{
+    decode_account,
+    error::RaffleError,
+    instructions::{self, CreateRaffleArgs},
+    pda::{find_counter_address, find_program_data_address, find_raffle_address},
+    types::{ConfigParams, Counter, RaffleAccount},
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    bpf_loader_upgradeable::UpgradeableLoaderState,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use randomness::RandomnessFixture;
+
+pub const FEE_BPS: u16 = 250;
+pub const BOND_BPS: u16 = 1_000;
+pub const DISPUTE_WINDOW_SECONDS: i64 = 3 * 24 * 60 * 60;
+pub const SHIPPING_WINDOW_SECONDS: i64 = 14 * 24 * 60 * 60;
+pub const RANDOMNESS_TIMEOUT_SLOTS: u64 = 1_500;
+
+/// One whole token of the payment mint.
+pub const TOKEN: u64 = 1_000_000;
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+}
+
+impl Harness {
+    /// Starts the SVM with the counter and config initialised and the
+    /// payment mint allowed. The test payer is the admin and arbiter.
+    pub async fn start() -> Self {
+        Self::initialise(Self::start_uninitialised().await).await
+    }
+
+    /// Like `start`, but deploys `target/deploy/Raffle_test_randomness.so`,
+    /// the program built with the `test-randomness` feature, so raffles can
+    /// draw from `RandomnessSource::Deterministic`.
+    pub async fn start_with_test_randomness() -> Self {
+        Self::initialise(Self::deploy("Raffle_test_randomness").await).await
+    }
+
+    async fn initialise(mut harness: Self) -> Self {
+        let (admin, mint, treasury) = (harness.admin, harness.mint, harness.treasury);
+        harness
+            .send(
+                &[
+                    instructions::initialise_counter(&admin),
+                    instructions::initialise_config(&admin, config_params(admin)),
+                    instructions::add_payment_mint(&admin, &mint, &treasury),
+                ],
+                &[],
+            )
+            .await;
+        harness
+    }
+
+    /// Starts the SVM with the program deployed as upgradeable, its upgrade
+    /// authority handed to the test payer, and the payment mint and treasury
+    /// created, but no counter or config yet.
+    pub async fn start_uninitialised() -> Self {
+        Self::deploy("Raffle").await
+    }
+
+    /// Deploys `target/deploy/<program_name>.so` at the program id.
+    async fn deploy(program_name: &'static str) -> Self {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            let deploy = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy");
+            std::env::set_var("SBF_OUT_DIR", deploy);
+        }
+        let program = std::path::Path::new(&std::env::var_os("SBF_OUT_DIR").unwrap())
+            .join(format!("{program_name}.so"));
+        assert!(
+            program.exists(),
+            "{} is missing: build it as the README describes before the ignored tests",
+            program.display()
+        );
+        let mut program_test = ProgramTest::default();
+        program_test.add_upgradeable_program_to_genesis(program_name, &raffle_client::ID);
+        let ctx = program_test.start_with_context().await;
+        let admin = ctx.payer.pubkey();
+        let mut harness = Self {
+            ctx,
+            admin,
+            mint: Pubkey::default(),
+            treasury: Pubkey::default(),
+        };
+
+        harness.set_upgrade_authority(&admin).await;
+        harness.mint = harness.create_mint(6).await;
+        harness.treasury = harness.token_account(&admin, 0).await;
+        harness
+    }
+
+    /// Genesis deploys the program with `Pubkey::default()` as its upgrade
+    /// authority; the authority is the last field of the metadata in front
+    /// of the ELF.
+    async fn set_upgrade_authority(&mut self, authority: &Pubkey) {
+        let address = find_program_data_address().0;
+        let mut program_data = self
+            .ctx
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        let metadata = UpgradeableLoaderState::size_of_programdata_metadata();
+        program_data.data[metadata - 32..metadata].copy_from_slice(authority.as_ref());
+        self.ctx
+            .set_account(&address, &AccountSharedData::from(program_data));
+    }
+
+    /// Sends `ixs` paid by the test payer, also signed by `signers`.
+    pub async fn try_send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut all: Vec<&Keypair> = vec![&self.ctx.payer];
+        all.extend_from_slice(signers);
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.ctx.payer.pubkey()),
+            &all,
+            blockhash,
+        );
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) {
+        self.try_send(ixs, signers).await.unwrap();
+    }
+
+    /// Creates a mint with `decimals`, the test payer as mint authority.
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &payer,
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[&mint]).await;
+        mint.pubkey()
+    }
+
+    /// Creates a token account of the payment mint holding `amount`.
+    pub async fn token_account(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let mut ixs = vec![
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::ID,
+                &account.pubkey(),
+                &self.mint,
+                owner,
+            )
+            .unwrap(),
+        ];
+        if amount > 0 {
+            ixs.push(
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &self.mint,
+                    &account.pubkey(),
+                    &payer,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+        self.send(&ixs, &[&account]).await;
+        account.pubkey()
+    }
+
+    /// A funded wallet with a token account holding `tokens` whole tokens.
+    pub async fn user(&mut self, tokens: u64) -> (Keypair, Pubkey) {
+        let user = Keypair::new();
+        let ix =
+            system_instruction::transfer(&self.ctx.payer.pubkey(), &user.pubkey(), 1_000_000_000);
+        self.send(&[ix], &[]).await;
+        let token_account = self.token_account(&user.pubkey(), tokens * TOKEN).await;
+        (user, token_account)
+    }
+
+    pub async fn balance(&mut self, token_account: &Pubkey) -> u64 {
+        self.ctx
+            .banks_client
+            .get_packed_account_data::<spl_token::state::Account>(*token_account)
+            .await
+            .unwrap()
+            .amount
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+        decode_account(&account.data).unwrap()
+    }
+
+    pub async fn raffle(&mut self, address: &Pubkey) -> RaffleAccount {
+        self.account(address).await
+    }
+
+    /// Creates a raffle as `seller` and returns its address and state.
+    pub async fn create_raffle(
+        &mut self,
+        seller: &Keypair,
+        seller_tokens: &Pubkey,
+        args: CreateRaffleArgs,
+    ) -> (Pubkey, RaffleAccount) {
+        let raffle_id = self
+            .account::<Counter>(&find_counter_address().0)
+            .await
+            .counter;
+        let ix = instructions::create_raffle(
+            &seller.pubkey(),
+            seller_tokens,
+            &self.mint,
+            raffle_id,
+            args,
+        );
+        self.send(&[ix], &[seller]).await;
+        let address = find_raffle_address(&seller.pubkey(), raffle_id).0;
+        (address, self.raffle(&address).await)
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar().await.unwrap()
+    }
+
+    /// Moves the bank `slots` ahead and the clock `seconds` ahead.
+    pub async fn warp(&mut self, slots: u64, seconds: i64) -> Clock {
+        let mut clock = self.clock().await;
+        clock.slot += slots;
+        clock.unix_timestamp += seconds;
+        self.ctx.warp_to_slot(clock.slot).unwrap();
+        self.ctx.set_sysvar(&clock);
+        clock
+    }
+
+    /// Moves one slot ahead with the clock one second past `timestamp`.
+    pub async fn warp_past(&mut self, timestamp: i64) -> Clock {
+        let now = self.clock().await.unix_timestamp;
+        self.warp(1, timestamp + 1 - now).await
+    }
+
+    /// Writes a Switchboard randomness account at `address`.
+    pub fn set_randomness(&mut self, address: &Pubkey, fixture: &RandomnessFixture) {
+        self.ctx
+            .set_account(address, &AccountSharedData::from(fixture.account()));
+    }
+}
+
+pub fn config_params(arbiter: Pubkey) -> ConfigParams {
+    ConfigParams {
+        arbiter,
+        fee_bps: FEE_BPS,
+        bond_bps: BOND_BPS,
+        dispute_window_seconds: DISPUTE_WINDOW_SECONDS,
+        shipping_window_seconds: SHIPPING_WINDOW_SECONDS,
+        randomness_timeout_slots: RANDOMNESS_TIMEOUT_SLOTS,
+    }
+}
+
+/// Asserts a transaction failed with `error` from the program.
+pub fn assert_raffle_error(result: Result<(), BanksClientError>, error: RaffleError) {
+    let code = u32::from(error);
+    match result.expect_err("transaction succeeded").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code)
+        }
+        other => panic!("expected custom error {code}, got {other:?}"),
+    }
+}
@@ -0,0 +1,53 @@
+//! Stand-in for a Switchboard on-demand randomness account, so `request_draw`
+//! and `draw_winner` run without the Switchboard program or its oracles.
+
+use bytemuck::Zeroable;
+use solana_sdk::account::Account;
+use switchboard_on_demand::{accounts::RandomnessAccountData, Discriminator, Owner};
+
+/// Builds the account the oracle would leave behind: committed to
+/// `seed_slot`, and once revealed, holding `value` from `reveal_slot` on.
+/// `draw_winner` only reads the value in exactly `reveal_slot`, and
+/// `request_draw` only accepts an account seeded in the previous slot.
+#[derive(Clone, Copy, Debug)]
+pub struct RandomnessFixture {
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl RandomnessFixture {
+    /// Committed at `seed_slot` and not revealed yet.
+    pub fn committed(seed_slot: u64) -> Self {
+        Self {
+            seed_slot,
+            reveal_slot: 0,
+            value: [0; 32],
+        }
+    }
+
+    pub fn revealed(self, reveal_slot: u64, value: [u8; 32]) -> Self {
+        Self {
+            reveal_slot,
+            value,
+            ..self
+        }
+    }
+
+    pub fn account(&self) -> Account {
+        let mut randomness = RandomnessAccountData::zeroed();
+        randomness.seed_slot = self.seed_slot;
+        randomness.reveal_slot = self.reveal_slot;
+        randomness.value = self.value;
+
+        let mut data = RandomnessAccountData::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&randomness));
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: RandomnessAccountData::owner(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
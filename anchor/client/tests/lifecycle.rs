@@ -0,0 +1,507 @@
+//! Full raffle lifecycles in an in-process SVM, with Switchboard replaced by
+//! `RandomnessFixture` accounts and the clock warped between steps.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use raffle_client::{
+    constants::ENTROPY_REVEAL_WINDOW_SECONDS,
+    error::RaffleError,
+    instructions::{self, CreateRaffleArgs},
+    pda::{find_draw_record_address, find_participant_page_address, find_raffle_archive_address},
+    randomness::entropy_commitment,
+    types::{
+        BondStatus, DeliveryStatus, DrawRecord, ParticipantPage, RaffleAccount, RaffleArchive,
+        RaffleStatus, RandomnessSource,
+    },
+};
+use solana_sdk::{
+    account::AccountSharedData,
+    hash::hashv,
+    signature::{Keypair, Signer},
+};
+
+use common::{
+    assert_raffle_error, randomness::RandomnessFixture, Harness, RANDOMNESS_TIMEOUT_SLOTS, TOKEN,
+};
+
+fn args(
+    deadline: i64,
+    min_tickets: u32,
+    max_tickets: u32,
+    source: RandomnessSource,
+) -> CreateRaffleArgs {
+    CreateRaffleArgs {
+        item_name: "Camera".to_string(),
+        item_description: "Film camera, boxed".to_string(),
+        item_image_uri: "https://example.com/camera.png".to_string(),
+        selling_price: 20,
+        ticket_price: 10,
+        min_tickets,
+        max_tickets,
+        deadline,
+        randomness_source: source,
+    }
+}
+
+/// Owner of `ticket` according to the raffle's first participant page.
+async fn ticket_owner(harness: &mut Harness, raffle: &Pubkey, ticket: u64) -> Pubkey {
+    let page: ParticipantPage = harness
+        .account(&find_participant_page_address(raffle, 0).0)
+        .await;
+    page.ticket_owner(ticket).unwrap()
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn switchboard_raffle_runs_from_creation_to_payout() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (alice, alice_tokens) = harness.user(100).await;
+    let (bob, bob_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 2, 4, RandomnessSource::Switchboard),
+        )
+        .await;
+    assert_eq!(harness.balance(&seller_tokens).await, 98 * TOKEN);
+
+    let buy = instructions::buy_tickets(&raffle, &alice.pubkey(), &alice_tokens, 1);
+    harness.send(&[buy], &[&alice]).await;
+    let buy = instructions::buy_tickets(&raffle, &bob.pubkey(), &bob_tokens, 3);
+    harness.send(&[buy], &[&bob]).await;
+
+    // Selling the last ticket closes sales, but the draw waits for the deadline
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Drawing);
+    assert!(raffle.is_sold_out);
+
+    let randomness = Pubkey::new_unique();
+    let slot = harness.clock().await.slot;
+    harness.set_randomness(
+        &randomness,
+        &RandomnessFixture::committed(slot.saturating_sub(1)),
+    );
+    let request = instructions::request_draw(&raffle, &harness.admin, Some(randomness));
+    let result = harness.try_send(&[request], &[]).await;
+    assert_raffle_error(result, RaffleError::DeadlineNotReached);
+
+    let clock = harness.warp_past(deadline).await;
+    let committed = RandomnessFixture::committed(clock.slot - 1);
+
+    // The same data in an account Switchboard does not own could be forged
+    let mut forged = committed.account();
+    forged.owner = seller.pubkey();
+    harness
+        .ctx
+        .set_account(&randomness, &AccountSharedData::from(forged));
+    let request = instructions::request_draw(&raffle, &harness.admin, Some(randomness));
+    let result = harness.try_send(&[request], &[]).await;
+    assert_raffle_error(result, RaffleError::InvalidRandomnessAccount);
+
+    harness.set_randomness(&randomness, &committed);
+    let request = instructions::request_draw(&raffle, &harness.admin, Some(randomness));
+    harness.send(&[request], &[]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.randomness_account, Some(randomness));
+    assert_eq!(raffle.randomness_commit_slot, Some(committed.seed_slot));
+
+    // The oracle reveals a few slots later; the draw lands in the reveal slot
+    let clock = harness.warp(3, 2).await;
+    let value = [42u8; 32];
+    harness.set_randomness(&randomness, &committed.revealed(clock.slot, value));
+    let ticket = RaffleAccount::pick_winning_ticket(&value, 4).unwrap();
+    let winner = ticket_owner(&mut harness, &raffle_key, ticket).await;
+
+    let draw = instructions::draw_winner(&raffle, &harness.admin, Some(randomness), 0);
+    harness.send(&[draw], &[]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winner, Some(winner));
+    assert_eq!(raffle.winning_ticket, Some(ticket));
+    let record: DrawRecord = harness
+        .account(&find_draw_record_address(&raffle_key).0)
+        .await;
+    assert_eq!(record.randomness, value);
+    assert_eq!(record.seed_slot, committed.seed_slot);
+    assert_eq!(record.reveal_slot, clock.slot);
+    assert_eq!(record.winner, winner);
+
+    let ship = instructions::mark_shipped(&raffle, Some("1Z999AA10123456784".to_string()));
+    harness.send(&[ship], &[&seller]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.product_delivered_status,
+        DeliveryStatus::Shipped
+    );
+
+    harness.warp(10, 2 * 24 * 60 * 60).await;
+    let winner_keypair: &Keypair = if winner == alice.pubkey() {
+        &alice
+    } else {
+        &bob
+    };
+    let deliver = instructions::mark_delivered(&raffle, &winner, None);
+    harness.send(&[deliver], &[winner_keypair]).await;
+
+    let release = instructions::release_to_seller(&raffle, &seller_tokens);
+    harness.send(&[release], &[&seller]).await;
+
+    // 40 tokens collected, 2.5% to the treasury, the rest and the bond to the seller
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.product_delivered_status, DeliveryStatus::Delivered);
+    assert!(raffle.payout_released);
+    assert_eq!(raffle.fee_collected, TOKEN);
+    assert_eq!(raffle.bond_status, BondStatus::Returned);
+    let treasury = harness.treasury;
+    assert_eq!(harness.balance(&treasury).await, TOKEN);
+    assert_eq!(harness.balance(&seller_tokens).await, 139 * TOKEN);
+    let escrow = instructions::escrow_address(&raffle);
+    assert_eq!(harness.balance(&escrow).await, 0);
+
+    // The keeper's crank closes the escrow; closing the raffle still takes it
+    let finalize = instructions::finalize_raffle(&raffle, &harness.admin, &seller_tokens);
+    harness.send(&[finalize], &[]).await;
+    let close = instructions::close_raffle(&raffle, &seller_tokens, true);
+    harness.send(&[close], &[&seller]).await;
+    let banks = &mut harness.ctx.banks_client;
+    assert!(banks.get_account(escrow).await.unwrap().is_none());
+    assert!(banks.get_account(raffle_key).await.unwrap().is_none());
+    let archive: RaffleArchive = harness
+        .account(&find_raffle_archive_address(&raffle_key).0)
+        .await;
+    assert_eq!(archive.status, RaffleStatus::Completed);
+    assert_eq!(archive.bond_status, BondStatus::Returned);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn commit_reveal_raffle_draws_from_participant_entropy() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (alice, alice_tokens) = harness.user(100).await;
+    let (bob, bob_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 2, 10, RandomnessSource::CommitReveal),
+        )
+        .await;
+
+    let secrets = [[1u8; 32], [2u8; 32]];
+    for ((buyer, tokens), secret) in [(&alice, alice_tokens), (&bob, bob_tokens)]
+        .into_iter()
+        .zip(secrets)
+    {
+        let commitment = entropy_commitment(&raffle_key, &buyer.pubkey(), &secret);
+        let ixs = [
+            instructions::buy_tickets(&raffle, &buyer.pubkey(), &tokens, 2),
+            instructions::commit_entropy(&raffle, &buyer.pubkey(), commitment),
+        ];
+        harness.send(&ixs, &[buyer]).await;
+    }
+
+    // Reveals open at the deadline
+    let reveal = instructions::reveal_entropy(&raffle, &alice.pubkey(), secrets[0]);
+    let result = harness.try_send(&[reveal], &[&alice]).await;
+    assert_raffle_error(result, RaffleError::DeadlineNotReached);
+
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+    for (buyer, secret) in [(&alice, secrets[0]), (&bob, secrets[1])] {
+        let reveal = instructions::reveal_entropy(&raffle, &buyer.pubkey(), secret);
+        harness.send(&[reveal], &[buyer]).await;
+    }
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Drawing);
+    assert_eq!(raffle.entropy, [3u8; 32]);
+    assert_eq!(raffle.entropy_reveals, 2);
+
+    let request = instructions::request_draw(&raffle, &harness.admin, None);
+    harness.send(&[request], &[]).await;
+    harness.warp(1, 1).await;
+    let draw = instructions::draw_winner(&raffle, &harness.admin, None, 0);
+    harness.send(&[draw], &[]).await;
+
+    let record: DrawRecord = harness
+        .account(&find_draw_record_address(&raffle_key).0)
+        .await;
+    let ticket = RaffleAccount::pick_winning_ticket(&record.randomness, 4).unwrap();
+    assert_eq!(record.winning_ticket, ticket);
+    assert_eq!(
+        record.winner,
+        ticket_owner(&mut harness, &raffle_key, ticket).await
+    );
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winner, Some(record.winner));
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle_test_randomness.so built with `test-randomness`"]
+async fn deterministic_raffle_draws_from_its_commit_slot() {
+    let mut harness = Harness::start_with_test_randomness().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (alice, alice_tokens) = harness.user(100).await;
+    let (bob, bob_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 2, 10, RandomnessSource::Deterministic),
+        )
+        .await;
+    for (buyer, tokens) in [(&alice, alice_tokens), (&bob, bob_tokens)] {
+        let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &tokens, 2);
+        harness.send(&[buy], &[buyer]).await;
+    }
+
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+    let request = instructions::request_draw(&raffle, &harness.admin, None);
+    harness.send(&[request], &[]).await;
+    let commit_slot = harness
+        .raffle(&raffle_key)
+        .await
+        .randomness_commit_slot
+        .unwrap();
+    harness.warp(1, 1).await;
+    let draw = instructions::draw_winner(&raffle, &harness.admin, None, 0);
+    harness.send(&[draw], &[]).await;
+
+    // Anyone can recompute the value from the raffle and its commit slot
+    let record: DrawRecord = harness
+        .account(&find_draw_record_address(&raffle_key).0)
+        .await;
+    let expected = hashv(&[raffle_key.as_ref(), &commit_slot.to_le_bytes()]).to_bytes();
+    assert_eq!(record.randomness, expected);
+    let ticket = RaffleAccount::pick_winning_ticket(&expected, 4).unwrap();
+    assert_eq!(record.winning_ticket, ticket);
+    assert_eq!(
+        record.winner,
+        ticket_owner(&mut harness, &raffle_key, ticket).await
+    );
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winner, Some(record.winner));
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn commit_reveal_raffle_without_reveals_is_abandoned_to_refunds() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (buyer, buyer_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 1, 10, RandomnessSource::CommitReveal),
+        )
+        .await;
+    let commitment = entropy_commitment(&raffle_key, &buyer.pubkey(), &[1u8; 32]);
+    let ixs = [
+        instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 2),
+        instructions::commit_entropy(&raffle, &buyer.pubkey(), commitment),
+    ];
+    harness.send(&ixs, &[&buyer]).await;
+
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+
+    // The buyer never reveals, so the draw can never be requested
+    let abandon = instructions::abandon_draw(&raffle, &harness.admin);
+    let result = harness.try_send(&[abandon], &[]).await;
+    assert_raffle_error(result, RaffleError::RevealWindowOpen);
+
+    harness
+        .warp_past(deadline + ENTROPY_REVEAL_WINDOW_SECONDS)
+        .await;
+    let request = instructions::request_draw(&raffle, &harness.admin, None);
+    let result = harness.try_send(&[request], &[]).await;
+    assert_raffle_error(result, RaffleError::NoEntropyRevealed);
+
+    let abandon = instructions::abandon_draw(&raffle, &harness.admin);
+    harness.send(&[abandon], &[]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.status,
+        RaffleStatus::Refunding
+    );
+
+    let refund = instructions::claim_refund(&raffle, &buyer.pubkey(), &buyer_tokens);
+    harness.send(&[refund], &[&buyer]).await;
+    let reclaim = instructions::reclaim_bond(&raffle, &seller_tokens);
+    harness.send(&[reclaim], &[&seller]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Refunded);
+    assert_eq!(raffle.bond_status, BondStatus::Returned);
+    assert_eq!(harness.balance(&buyer_tokens).await, 100 * TOKEN);
+    assert_eq!(harness.balance(&seller_tokens).await, 100 * TOKEN);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn unrevealed_switchboard_commitment_is_replaced_after_timeout() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (buyer, buyer_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 1, 2, RandomnessSource::Switchboard),
+        )
+        .await;
+    let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 2);
+    harness.send(&[buy], &[&buyer]).await;
+
+    let abandoned = Pubkey::new_unique();
+    let clock = harness.warp_past(deadline).await;
+    harness.set_randomness(&abandoned, &RandomnessFixture::committed(clock.slot - 1));
+    let request = instructions::request_draw(&raffle, &harness.admin, Some(abandoned));
+    harness.send(&[request], &[]).await;
+
+    let replacement = Pubkey::new_unique();
+    harness.set_randomness(&replacement, &RandomnessFixture::committed(clock.slot - 1));
+    let rerequest = instructions::rerequest_draw(&raffle, &harness.admin, &abandoned, &replacement);
+    let result = harness.try_send(&[rerequest], &[]).await;
+    assert_raffle_error(result, RaffleError::RandomnessStillPending);
+
+    // The oracle never answered; after the timeout a fresh commitment replaces it
+    let clock = harness.warp(RANDOMNESS_TIMEOUT_SLOTS + 1, 600).await;
+    let committed = RandomnessFixture::committed(clock.slot - 1);
+    harness.set_randomness(&replacement, &committed);
+
+    // Only the admin may throw the old commitment away
+    let rerequest =
+        instructions::rerequest_draw(&raffle, &buyer.pubkey(), &abandoned, &replacement);
+    let result = harness.try_send(&[rerequest], &[&buyer]).await;
+    assert_raffle_error(result, RaffleError::Unauthorized);
+
+    // A late reveal of the stored commitment is drawn, not replaced
+    let stale = harness
+        .raffle(&raffle_key)
+        .await
+        .randomness_commit_slot
+        .unwrap();
+    let late = RandomnessFixture::committed(stale).revealed(clock.slot, [9u8; 32]);
+    harness.set_randomness(&abandoned, &late);
+    let rerequest = instructions::rerequest_draw(&raffle, &harness.admin, &abandoned, &replacement);
+    let result = harness.try_send(&[rerequest], &[]).await;
+    assert_raffle_error(result, RaffleError::RandomnessAlreadyRevealed);
+    harness.set_randomness(&abandoned, &RandomnessFixture::committed(stale));
+
+    let rerequest = instructions::rerequest_draw(&raffle, &harness.admin, &abandoned, &replacement);
+    harness.send(&[rerequest], &[]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.randomness_account, Some(replacement));
+    assert_eq!(raffle.randomness_commit_slot, Some(committed.seed_slot));
+
+    // A reveal on the abandoned account no longer counts
+    let clock = harness.warp(1, 1).await;
+    let value = [7u8; 32];
+    harness.set_randomness(&abandoned, &committed.revealed(clock.slot, value));
+    let draw = instructions::draw_winner(&raffle, &harness.admin, Some(abandoned), 0);
+    let result = harness.try_send(&[draw], &[]).await;
+    assert_raffle_error(result, RaffleError::InvalidRandomnessAccount);
+
+    harness.set_randomness(&replacement, &committed.revealed(clock.slot, value));
+    let draw = instructions::draw_winner(&raffle, &harness.admin, Some(replacement), 0);
+    harness.send(&[draw], &[]).await;
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winner, Some(buyer.pubkey()));
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn recommitted_switchboard_account_is_replaced_after_timeout() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (buyer, buyer_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            args(deadline, 1, 2, RandomnessSource::Switchboard),
+        )
+        .await;
+    let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 2);
+    harness.send(&[buy], &[&buyer]).await;
+
+    let reused = Pubkey::new_unique();
+    let clock = harness.warp_past(deadline).await;
+    let stale = clock.slot - 1;
+    harness.set_randomness(&reused, &RandomnessFixture::committed(stale));
+    let request = instructions::request_draw(&raffle, &harness.admin, Some(reused));
+    harness.send(&[request], &[]).await;
+
+    // Whoever holds the account commits it again before the old seed is
+    // revealed, so the stored commitment can never be drawn
+    let clock = harness.warp(5, 5).await;
+    let recommitted = RandomnessFixture::committed(clock.slot - 1);
+    harness.set_randomness(&reused, &recommitted.revealed(clock.slot, [4u8; 32]));
+    let draw = instructions::draw_winner(&raffle, &harness.admin, Some(reused), 0);
+    let result = harness.try_send(&[draw], &[]).await;
+    assert_raffle_error(result, RaffleError::RandomnessExpired);
+
+    let replacement = Pubkey::new_unique();
+    harness.set_randomness(&replacement, &RandomnessFixture::committed(clock.slot - 1));
+    let rerequest = instructions::rerequest_draw(&raffle, &harness.admin, &reused, &replacement);
+    let result = harness.try_send(&[rerequest], &[]).await;
+    assert_raffle_error(result, RaffleError::RandomnessStillPending);
+
+    let clock = harness.warp(RANDOMNESS_TIMEOUT_SLOTS + 1, 600).await;
+    let committed = RandomnessFixture::committed(clock.slot - 1);
+    harness.set_randomness(&replacement, &committed);
+    let rerequest = instructions::rerequest_draw(&raffle, &harness.admin, &reused, &replacement);
+    harness.send(&[rerequest], &[]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.randomness_commit_slot,
+        Some(committed.seed_slot)
+    );
+
+    let clock = harness.warp(1, 1).await;
+    harness.set_randomness(&replacement, &committed.revealed(clock.slot, [7u8; 32]));
+    let draw = instructions::draw_winner(&raffle, &harness.admin, Some(replacement), 0);
+    harness.send(&[draw], &[]).await;
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Completed);
+    assert_eq!(raffle.winner, Some(buyer.pubkey()));
+}
+
+#[test]
+fn fixture_parses_as_switchboard_randomness() {
+    use std::cell::RefCell;
+    use switchboard_on_demand::accounts::RandomnessAccountData;
+
+    let fixture = RandomnessFixture::committed(99).revealed(101, [5u8; 32]);
+    let mut data = fixture.account().data;
+    let cell = RefCell::new(&mut data[..]);
+    let parsed = RandomnessAccountData::parse(cell.borrow()).unwrap();
+    assert_eq!(parsed.seed_slot, 99);
+    assert_eq!(parsed.get_value(101).unwrap(), [5u8; 32]);
+    assert!(parsed.get_value(102).is_err());
+}
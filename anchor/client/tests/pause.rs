@@ -0,0 +1,215 @@
+//! `PAUSE_PAYOUT` holds every instruction that pays escrow out to the seller,
+//! and each one goes through again once the flag is lifted. The ways into
+//! `Refunding` stay open so a pause never traps participants' tickets.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use raffle_client::{
+    constants::PAUSE_PAYOUT,
+    error::RaffleError,
+    instructions::{self, CreateRaffleArgs},
+    randomness::entropy_commitment,
+    types::{
+        BondStatus, DeliveryStatus, DisputeResolution, RaffleAccount, RaffleStatus,
+        RandomnessSource,
+    },
+};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+use common::{assert_raffle_error, Harness};
+
+fn args(deadline: i64, min_tickets: u32) -> CreateRaffleArgs {
+    CreateRaffleArgs {
+        item_name: "Camera".to_string(),
+        item_description: "Film camera, boxed".to_string(),
+        item_image_uri: "https://example.com/camera.png".to_string(),
+        selling_price: 20,
+        ticket_price: 10,
+        min_tickets,
+        max_tickets: 10,
+        deadline,
+        randomness_source: RandomnessSource::CommitReveal,
+    }
+}
+
+/// Sends `ix` while payouts are paused, expecting `ProgramPaused`, then again
+/// after unpausing. The retry moves a slot ahead so it gets a fresh blockhash
+/// instead of repeating the failed signature.
+async fn assert_held_by_pause(harness: &mut Harness, ix: Instruction, signers: &[&Keypair]) {
+    let pause = instructions::pause(&harness.admin, PAUSE_PAYOUT);
+    harness.send(&[pause], &[]).await;
+    let result = harness.try_send(std::slice::from_ref(&ix), signers).await;
+    assert_raffle_error(result, RaffleError::ProgramPaused);
+
+    let unpause = instructions::unpause(&harness.admin, PAUSE_PAYOUT);
+    harness.send(&[unpause], &[]).await;
+    harness.warp(1, 0).await;
+    harness.send(&[ix], signers).await;
+}
+
+/// A commit-reveal raffle with a single buyer, drawn and waiting for the
+/// seller to ship. Returns the raffle address and the winner with their
+/// token account.
+async fn drawn_raffle(
+    harness: &mut Harness,
+    seller: &Keypair,
+    seller_tokens: &Pubkey,
+) -> (Pubkey, Keypair, Pubkey) {
+    let (winner, winner_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(seller, seller_tokens, args(deadline, 1))
+        .await;
+    let secret = [7u8; 32];
+    let commitment = entropy_commitment(&raffle_key, &winner.pubkey(), &secret);
+    let ixs = [
+        instructions::buy_tickets(&raffle, &winner.pubkey(), &winner_tokens, 1),
+        instructions::commit_entropy(&raffle, &winner.pubkey(), commitment),
+    ];
+    harness.send(&ixs, &[&winner]).await;
+
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+    let reveal = instructions::reveal_entropy(&raffle, &winner.pubkey(), secret);
+    harness.send(&[reveal], &[&winner]).await;
+    let request = instructions::request_draw(&raffle, &harness.admin, None);
+    harness.send(&[request], &[]).await;
+    harness.warp(1, 1).await;
+    let draw = instructions::draw_winner(&raffle, &harness.admin, None, 0);
+    harness.send(&[draw], &[]).await;
+
+    let raffle: RaffleAccount = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.winner, Some(winner.pubkey()));
+    (raffle_key, winner, winner_tokens)
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn pause_holds_reclaim_bond() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (buyer, buyer_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(&seller, &seller_tokens, args(deadline, 2))
+        .await;
+    let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 1);
+    harness.send(&[buy], &[&buyer]).await;
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.status,
+        RaffleStatus::Refunding
+    );
+
+    let reclaim = instructions::reclaim_bond(&raffle, &seller_tokens);
+    assert_held_by_pause(&mut harness, reclaim, &[&seller]).await;
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn pause_holds_close_raffle() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (buyer, buyer_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(&seller, &seller_tokens, args(deadline, 2))
+        .await;
+    let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 1);
+    harness.send(&[buy], &[&buyer]).await;
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+    let refund = instructions::claim_refund(&raffle, &buyer.pubkey(), &buyer_tokens);
+    harness.send(&[refund], &[&buyer]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.status,
+        RaffleStatus::Refunded
+    );
+
+    // The unclaimed bond is still in escrow and goes back with the close
+    let seller_before = harness.balance(&seller_tokens).await;
+    let close = instructions::close_raffle(&raffle, &seller_tokens, false);
+    assert_held_by_pause(&mut harness, close, &[&seller]).await;
+    let closed = harness
+        .ctx
+        .banks_client
+        .get_account(raffle_key)
+        .await
+        .unwrap();
+    assert!(closed.is_none());
+    assert_eq!(
+        harness.balance(&seller_tokens).await,
+        seller_before + raffle.bond_amount
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn pause_leaves_expire_unshipped_open() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (raffle_key, winner, winner_tokens) =
+        drawn_raffle(&mut harness, &seller, &seller_tokens).await;
+    let raffle = harness.raffle(&raffle_key).await;
+
+    harness.warp_past(raffle.shipping_deadline.unwrap()).await;
+    let pause = instructions::pause(&harness.admin, PAUSE_PAYOUT);
+    harness.send(&[pause], &[]).await;
+    let expire = instructions::expire_unshipped(&raffle, &winner.pubkey(), &winner_tokens);
+    let winner_before = harness.balance(&winner_tokens).await;
+    harness.send(&[expire], &[&winner]).await;
+    let expired = harness.raffle(&raffle_key).await;
+    assert_eq!(expired.status, RaffleStatus::Refunding);
+    assert_eq!(expired.bond_status, BondStatus::Slashed);
+    assert_eq!(
+        harness.balance(&winner_tokens).await,
+        winner_before + raffle.bond_amount
+    );
+
+    // Refunds are never paused, so the winner gets their ticket back too
+    let refund = instructions::claim_refund(&raffle, &winner.pubkey(), &winner_tokens);
+    harness.send(&[refund], &[&winner]).await;
+    assert_eq!(
+        harness.raffle(&raffle_key).await.status,
+        RaffleStatus::Refunded
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn pause_leaves_resolve_dispute_open() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+    let (raffle_key, winner, winner_tokens) =
+        drawn_raffle(&mut harness, &seller, &seller_tokens).await;
+    let raffle = harness.raffle(&raffle_key).await;
+
+    let ship = instructions::mark_shipped(&raffle, Some("TRACK-1".to_string()));
+    harness.send(&[ship], &[&seller]).await;
+    let dispute = instructions::open_dispute(&raffle, &winner.pubkey(), [9u8; 32]);
+    harness.send(&[dispute], &[&winner]).await;
+
+    let pause = instructions::pause(&harness.admin, PAUSE_PAYOUT);
+    harness.send(&[pause], &[]).await;
+    let resolve = instructions::resolve_dispute(
+        &raffle,
+        &harness.admin,
+        &winner_tokens,
+        DisputeResolution::RefundParticipants,
+    );
+    harness.send(&[resolve], &[]).await;
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.product_delivered_status, DeliveryStatus::Resolved);
+    assert_eq!(raffle.status, RaffleStatus::Refunding);
+}
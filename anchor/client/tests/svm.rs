@@ -0,0 +1,163 @@
+//! Runs the client's builders against the program in an in-process SVM.
+
+mod common;
+
+use raffle_client::{
+    decode_counter,
+    error::RaffleError,
+    instructions::{self, CreateRaffleArgs},
+    pda::find_counter_address,
+    types::{RaffleStatus, RandomnessSource},
+};
+use solana_sdk::signature::Signer;
+
+use common::{assert_raffle_error, config_params, Harness, TOKEN};
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn only_the_upgrade_authority_initialises_the_config() {
+    let mut harness = Harness::start_uninitialised().await;
+    let (intruder, _) = harness.user(0).await;
+
+    let init =
+        instructions::initialise_config(&intruder.pubkey(), config_params(intruder.pubkey()));
+    let result = harness.try_send(&[init], &[&intruder]).await;
+    assert_raffle_error(result, RaffleError::Unauthorized);
+
+    let admin = harness.admin;
+    let init = instructions::initialise_config(&admin, config_params(admin));
+    harness.send(&[init], &[]).await;
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn create_buy_and_close_sales() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (raffle_key, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            CreateRaffleArgs {
+                item_name: "Camera".to_string(),
+                item_description: "Film camera, boxed".to_string(),
+                item_image_uri: "https://example.com/camera.png".to_string(),
+                selling_price: 100,
+                ticket_price: 10,
+                min_tickets: 5,
+                max_tickets: 20,
+                deadline,
+                randomness_source: RandomnessSource::CommitReveal,
+            },
+        )
+        .await;
+
+    let counter = harness
+        .ctx
+        .banks_client
+        .get_account(find_counter_address().0)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(decode_counter(&counter.data).unwrap().counter, 1);
+    assert_eq!(raffle.seller, seller.pubkey());
+    assert_eq!(raffle.ticket_price, 10 * TOKEN);
+    assert_eq!(raffle.bond_amount, 10 * TOKEN);
+    assert_eq!(raffle.treasury, harness.treasury);
+
+    let (buyer, buyer_tokens) = harness.user(100).await;
+    let buy = instructions::buy_tickets(&raffle, &buyer.pubkey(), &buyer_tokens, 3);
+    harness.send(&[buy], &[&buyer]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.total_entries, 3);
+    assert_eq!(raffle.total_collected, 30 * TOKEN);
+    let escrow = instructions::escrow_address(&raffle);
+    assert_eq!(harness.balance(&escrow).await, 40 * TOKEN);
+
+    // Nothing to ship before a winner is drawn
+    let ship = instructions::mark_shipped(&raffle, None);
+    let result = harness.try_send(&[ship], &[&seller]).await;
+    assert_raffle_error(result, RaffleError::RaffleNotCompleted);
+
+    // Three of five tickets sold: closing sales after the deadline refunds
+    harness.warp_past(deadline).await;
+    let close = instructions::close_sales(&raffle, &harness.admin);
+    harness.send(&[close], &[]).await;
+
+    let raffle = harness.raffle(&raffle_key).await;
+    assert_eq!(raffle.status, RaffleStatus::Refunding);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn prices_follow_the_payment_mint_decimals() {
+    let mut harness = Harness::start().await;
+    let admin = harness.admin;
+    harness.mint = harness.create_mint(9).await;
+    harness.treasury = harness.token_account(&admin, 0).await;
+    let (mint, treasury) = (harness.mint, harness.treasury);
+    let add = instructions::add_payment_mint(&admin, &mint, &treasury);
+    harness.send(&[add], &[]).await;
+
+    let one_token = 1_000_000_000;
+    let (seller, _) = harness.user(0).await;
+    let seller_tokens = harness
+        .token_account(&seller.pubkey(), 100 * one_token)
+        .await;
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let (_, raffle) = harness
+        .create_raffle(
+            &seller,
+            &seller_tokens,
+            CreateRaffleArgs {
+                item_name: "Camera".to_string(),
+                item_description: "Film camera, boxed".to_string(),
+                item_image_uri: "https://example.com/camera.png".to_string(),
+                selling_price: 100,
+                ticket_price: 10,
+                min_tickets: 5,
+                max_tickets: 20,
+                deadline,
+                randomness_source: RandomnessSource::CommitReveal,
+            },
+        )
+        .await;
+
+    assert_eq!(raffle.payment_mint, mint);
+    assert_eq!(raffle.selling_price, 100 * one_token);
+    assert_eq!(raffle.ticket_price, 10 * one_token);
+    assert_eq!(raffle.bond_amount, 10 * one_token);
+    let escrow = instructions::escrow_address(&raffle);
+    assert_eq!(harness.balance(&escrow).await, 10 * one_token);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/Raffle.so from `anchor build`"]
+async fn deployable_build_rejects_deterministic_randomness() {
+    let mut harness = Harness::start().await;
+    let (seller, seller_tokens) = harness.user(100).await;
+
+    let deadline = harness.clock().await.unix_timestamp + 3_600;
+    let ix = instructions::create_raffle(
+        &seller.pubkey(),
+        &seller_tokens,
+        &harness.mint,
+        0,
+        CreateRaffleArgs {
+            item_name: "Camera".to_string(),
+            item_description: "Film camera, boxed".to_string(),
+            item_image_uri: "https://example.com/camera.png".to_string(),
+            selling_price: 100,
+            ticket_price: 10,
+            min_tickets: 5,
+            max_tickets: 20,
+            deadline,
+            randomness_source: RandomnessSource::Deterministic,
+        },
+    );
+    let result = harness.try_send(&[ix], &[&seller]).await;
+    assert_raffle_error(result, RaffleError::RandomnessSourceUnavailable);
+}
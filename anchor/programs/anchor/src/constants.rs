@@ -0,0 +1,29 @@
+/// Fees are expressed in basis points of this denominator.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Most payment mints `ProgramConfig` can allow at once.
+pub const MAX_PAYMENT_MINTS: usize = 8;
+
+/// How long after the raffle deadline participants of a commit-reveal raffle
+/// have to reveal their entropy before the draw may proceed without them.
+pub const ENTROPY_REVEAL_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// Version of the winner selection recorded in every `DrawRecord`.
+/// 1: `RaffleAccount::pick_winning_ticket` rejection sampling over the
+/// revealed bytes, then the owner of that ticket in the participant pages.
+pub const DRAW_ALGORITHM_VERSION: u8 = 1;
+
+/// Bits of `ProgramConfig::paused`, one per pausable instruction group.
+/// Refunds deliberately have no bit so participants can always get out.
+pub const PAUSE_CREATE_RAFFLE: u16 = 1 << 0;
+pub const PAUSE_BUY_TICKETS: u16 = 1 << 1;
+/// Covers `request_draw` and `rerequest_draw`.
+pub const PAUSE_REQUEST_DRAW: u16 = 1 << 2;
+pub const PAUSE_DRAW_WINNER: u16 = 1 << 3;
+/// Covers the instructions that pay escrow out to the seller:
+/// `release_to_seller`, `finalize_raffle`, `reclaim_bond` and `close_raffle`.
+/// `expire_unshipped` and `resolve_dispute` stay open, as they are the only
+/// way into `Refunding` once a winner is drawn.
+pub const PAUSE_PAYOUT: u16 = 1 << 4;
+pub const PAUSE_ALL: u16 =
+    PAUSE_CREATE_RAFFLE | PAUSE_BUY_TICKETS | PAUSE_REQUEST_DRAW | PAUSE_DRAW_WINNER | PAUSE_PAYOUT;
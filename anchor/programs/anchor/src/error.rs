@@ -63,5 +63,61 @@ pub enum RaffleError {
     #[msg("Randomness already requested!")]
     RandomnessAlreadyRequested,
     #[msg("Randomness has not been requested yet!")]
-    RandomnessNotRequested
+    RandomnessNotRequested,
+    #[msg("Signer is not a participant of this raffle!")]
+    NotParticipant,
+    #[msg("Delivery has not been confirmed yet!")]
+    DeliveryNotConfirmed,
+    #[msg("A dispute is open for this raffle!")]
+    DisputeOpen,
+    #[msg("Dispute window has closed!")]
+    DisputeWindowClosed,
+    #[msg("No dispute is open for this raffle!")]
+    NoOpenDispute,
+    #[msg("Participant page is full!")]
+    ParticipantPageFull,
+    #[msg("Winning ticket is not on this participant page!")]
+    WrongParticipantPage,
+    #[msg("Randomness commitment is still within its reveal window!")]
+    RandomnessStillPending,
+    #[msg("Randomness source is not available in this build!")]
+    RandomnessSourceUnavailable,
+    #[msg("Raffle does not use this randomness source!")]
+    WrongRandomnessSource,
+    #[msg("Entropy already committed!")]
+    EntropyAlreadyCommitted,
+    #[msg("Revealed secret does not match the commitment!")]
+    InvalidEntropyReveal,
+    #[msg("No participant has revealed entropy yet!")]
+    NoEntropyRevealed,
+    #[msg("Participants are still revealing entropy!")]
+    RevealWindowOpen,
+    #[msg("Fee and bond must not exceed 10000 basis points!")]
+    InvalidFee,
+    #[msg("Invalid config parameters!")]
+    InvalidConfig,
+    #[msg("Payment mint is not allowed!")]
+    PaymentMintNotAllowed,
+    #[msg("Payment mint is already allowed!")]
+    PaymentMintAlreadyAllowed,
+    #[msg("Too many payment mints!")]
+    TooManyPaymentMints,
+    #[msg("Treasury does not hold the payment mint!")]
+    InvalidTreasury,
+    #[msg("This instruction is paused!")]
+    ProgramPaused,
+    #[msg("Unknown pause flags!")]
+    InvalidPauseFlags,
+    #[msg("Seller bond is not held in escrow!")]
+    BondNotHeld,
+    #[msg("Seller bond can only be reclaimed after a raffle that ended without a winner!")]
+    BondNotReclaimable,
+    #[msg("Shipping deadline has passed!")]
+    ShippingDeadlinePassed,
+    #[msg("Shipping deadline not reached yet!")]
+    ShippingDeadlineNotReached,
+    #[msg("Raffle is not settled yet!")]
+    RaffleNotSettled,
+    #[msg("A participant revealed entropy, the draw can go ahead!")]
+    EntropyWasRevealed,
 }
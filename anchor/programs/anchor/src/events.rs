@@ -1,6 +1,8 @@
 use anchor_lang::prelude::Pubkey;
 use anchor_lang::prelude::*;
 
+use crate::types::{DisputeResolution, RaffleStatus, RandomnessSource};
+
 #[event]
 #[derive(Debug)]
 pub struct RaffleCreated {
@@ -8,9 +10,11 @@ pub struct RaffleCreated {
     pub seller: Pubkey,
     pub ticket_price:u64,
     pub deadline:i64,
+    pub bond_amount: u64,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct TicketsBought {
     pub buyer: Pubkey,
     pub raffle: Pubkey,
@@ -35,3 +39,180 @@ pub struct  ProductDelivered {
     pub delivered_at: Option<i64>,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct RefundClaimed {
+    pub raffle: Pubkey,
+    pub buyer: Pubkey,
+    pub tickets: u32,
+    pub amount: u64,
+    pub total_refunded: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SellerPaid {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64, // Paid to the seller, after the platform fee
+    pub fee: u64,
+    pub bond_returned: u64,
+    pub treasury: Pubkey,
+    pub selling_price: u64,
+    pub surplus: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RaffleCancelled {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub raffle_id: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DisputeOpened {
+    pub raffle: Pubkey,
+    pub winner: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub opened_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DisputeResolved {
+    pub raffle: Pubkey,
+    pub arbiter: Pubkey,
+    pub resolution: DisputeResolution,
+    pub resolved_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RandomnessRerequested {
+    pub raffle: Pubkey,
+    pub abandoned_randomness_account: Pubkey,
+    pub abandoned_commit_slot: u64,
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct EntropyCommitted {
+    pub raffle: Pubkey,
+    pub buyer: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+#[derive(Debug)]
+pub struct EntropyRevealed {
+    pub raffle: Pubkey,
+    pub buyer: Pubkey,
+    pub reveals: u32,
+    pub commitments: u32,
+}
+
+/// A commit-reveal raffle whose reveal window closed with no reveals; every
+/// buyer can claim a refund.
+#[event]
+#[derive(Debug)]
+pub struct DrawAbandoned {
+    pub raffle: Pubkey,
+    pub entropy_commitments: u32,
+    pub abandoned_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct WinnerDrawn {
+    pub raffle: Pubkey,
+    pub randomness_source: RandomnessSource,
+    pub randomness_account: Pubkey,
+    pub randomness: [u8; 32],
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub total_entries: u64,
+    pub winning_ticket: u64,
+    pub winner: Pubkey,
+    pub participant_page: u32,
+    pub algorithm_version: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ProgramPaused {
+    pub admin: Pubkey,
+    pub flags: u16,  // Flags set by this call
+    pub paused: u16, // Whole bitmask afterwards
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ProgramUnpaused {
+    pub admin: Pubkey,
+    pub flags: u16,  // Flags cleared by this call
+    pub paused: u16, // Whole bitmask afterwards
+}
+
+#[event]
+#[derive(Debug)]
+pub struct BondReturned {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct BondSlashed {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ShippingExpired {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Pubkey,
+    pub shipping_deadline: i64,
+    pub bond_slashed: u64,
+    pub expired_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RaffleSettled {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub total_collected: u64,
+    pub total_refunded: u64,
+    pub fee: u64,
+    pub leftover: u64, // Stray escrow balance swept to the seller
+    pub settled_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RaffleClosed {
+    pub raffle: Pubkey,
+    pub seller: Pubkey,
+    pub raffle_id: u64,
+    pub status: RaffleStatus,
+    pub archived: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SalesClosed {
+    pub raffle: Pubkey,
+    pub total_entries: u64,
+    pub min_tickets: u32,
+    pub status: RaffleStatus, // Drawing, or Refunding/Refunded if min_tickets was missed
+    pub closed_at: i64,
+}
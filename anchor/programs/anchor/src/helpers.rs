@@ -1,5 +1,163 @@
-use anchor_lang::prelude::{Clock, SolanaSysvar};
+use std::cell::Ref;
+
+use anchor_lang::prelude::{
+    Account, AccountInfo, Clock, CpiContext, Program, Result, SolanaSysvar, ToAccountInfo,
+};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use switchboard_on_demand::{accounts::RandomnessAccountData, Owner};
+
+use crate::{
+    error::RaffleError,
+    pda::RAFFLE_SEED,
+    types::{BondStatus, RaffleAccount},
+    utils::bps_of,
+};
 
 pub fn get_unix_timestamp() -> i64 {
     Clock::get().unwrap().unix_timestamp
-}
\ No newline at end of file
+}
+
+/// Parses a Switchboard randomness account. `RandomnessAccountData::parse`
+/// only checks the discriminator, so an account owned by anyone but the
+/// on-demand program could carry forged slots and values.
+pub fn load_randomness<'a>(
+    randomness_account_data: &'a AccountInfo,
+) -> Result<Ref<'a, RandomnessAccountData>> {
+    if *randomness_account_data.owner != RandomnessAccountData::owner() {
+        return Err(RaffleError::InvalidRandomnessAccount.into());
+    }
+    RandomnessAccountData::parse(randomness_account_data.data.borrow())
+        .map_err(|_| RaffleError::InvalidRandomnessAccount.into())
+}
+
+/// Validates a Switchboard randomness account committed in the previous slot
+/// and not yet revealed, returning its seed slot.
+pub fn fresh_commit_slot(randomness_account_data: &AccountInfo, clock: &Clock) -> Result<u64> {
+    let randomness_data = load_randomness(randomness_account_data)?;
+
+    if randomness_data.seed_slot != clock.slot.saturating_sub(1) {
+        return Err(RaffleError::RandomnessExpired.into());
+    }
+    if randomness_data.get_value(clock.slot).is_ok() {
+        return Err(RaffleError::RandomnessAlreadyRevealed.into());
+    }
+    Ok(randomness_data.seed_slot)
+}
+
+/// Runs `f` with the raffle PDA's signer seeds; the raffle is the escrow
+/// authority.
+fn with_raffle_seeds<T>(
+    raffle: &RaffleAccount,
+    f: impl FnOnce(&[&[&[u8]]]) -> Result<T>,
+) -> Result<T> {
+    let raffle_id_bytes = raffle.raffle_id.to_le_bytes();
+    f(&[&[
+        RAFFLE_SEED,
+        raffle.seller.as_ref(),
+        &raffle_id_bytes,
+        &[raffle.bump],
+    ]])
+}
+
+/// Moves `amount` out of the raffle escrow, signed by the raffle PDA.
+pub fn transfer_from_escrow<'info>(
+    raffle: &Account<'info, RaffleAccount>,
+    escrow_payment_account: &impl ToAccountInfo<'info>,
+    to: &impl ToAccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: escrow_payment_account.to_account_info(),
+        to: to.to_account_info(),
+        authority: raffle.to_account_info(),
+    };
+    with_raffle_seeds(raffle, |signer_seeds| {
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)
+    })
+}
+
+/// Closes the (empty) raffle escrow and sends its rent to `destination`.
+pub fn close_escrow<'info>(
+    raffle: &Account<'info, RaffleAccount>,
+    escrow_payment_account: &impl ToAccountInfo<'info>,
+    destination: &impl ToAccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: escrow_payment_account.to_account_info(),
+        destination: destination.to_account_info(),
+        authority: raffle.to_account_info(),
+    };
+    with_raffle_seeds(raffle, |signer_seeds| {
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::close_account(cpi_ctx)
+    })
+}
+
+/// What `pay_out_seller` moved out of escrow.
+pub struct Payout {
+    pub amount: u64, // To the seller, after the platform fee
+    pub fee: u64,
+    pub bond_returned: u64,
+    pub surplus: u64,
+}
+
+/// Pays the escrowed pot to the seller: the platform fee goes to the treasury,
+/// the rest plus a still-held bond to `seller_token_account`. Marks the raffle
+/// paid out. Callers check `RaffleAccount::check_payout_ready` first.
+pub fn pay_out_seller<'info>(
+    raffle: &mut Account<'info, RaffleAccount>,
+    escrow_payment_account: &Account<'info, TokenAccount>,
+    seller_token_account: &Account<'info, TokenAccount>,
+    treasury: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<Payout> {
+    let amount = raffle
+        .total_collected
+        .checked_sub(raffle.total_refunded)
+        .ok_or(RaffleError::UnderFlow)?;
+    let surplus = amount.saturating_sub(raffle.selling_price);
+    let fee = bps_of(amount, raffle.fee_bps)?;
+    let seller_amount = amount.checked_sub(fee).ok_or(RaffleError::UnderFlow)?;
+    let bond_returned = if raffle.bond_status == BondStatus::Held {
+        raffle.bond_amount
+    } else {
+        0
+    };
+
+    if fee > 0 {
+        transfer_from_escrow(raffle, escrow_payment_account, treasury, token_program, fee)?;
+    }
+    transfer_from_escrow(
+        raffle,
+        escrow_payment_account,
+        seller_token_account,
+        token_program,
+        seller_amount
+            .checked_add(bond_returned)
+            .ok_or(RaffleError::Overflow)?,
+    )?;
+
+    raffle.payout_released = true;
+    raffle.fee_collected = fee;
+    if raffle.bond_status == BondStatus::Held {
+        raffle.bond_status = BondStatus::Returned;
+    }
+
+    Ok(Payout {
+        amount: seller_amount,
+        fee,
+        bond_returned,
+        surplus,
+    })
+}
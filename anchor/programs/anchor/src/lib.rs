@@ -1,20 +1,36 @@
+// rustc only reads the crate-name lint at the root; the `Raffle` name is
+// what the IDL and `target/deploy/Raffle.so` are built under.
+#![allow(non_snake_case)]
+
 use crate::{
     error::RaffleError,
-    events::{ProductDelivered, ProductShipped},
-    types::{Counter, RaffleAccount},
+    events::{
+        BondReturned, BondSlashed, DisputeOpened, DisputeResolved, ProductDelivered,
+        ProductShipped, RaffleCancelled, SellerPaid,
+    },
+    pda::{
+        CONFIG_SEED, COUNTER_SEED, DRAW_RECORD_SEED, ESCROW_SEED, PARTICIPANT_PAGE_SEED,
+        RAFFLE_ARCHIVE_SEED, RAFFLE_SEED, TICKET_RECEIPT_SEED,
+    },
+    types::{
+        BondStatus, ConfigParams, Counter, DisputeResolution, DrawRecord, ParticipantPage,
+        ProgramConfig, RaffleAccount, RaffleArchive, RandomnessSource, TicketReceipt,
+    },
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Token, TokenAccount, Transfer},
 };
-use switchboard_on_demand::accounts::RandomnessAccountData;
 
 declare_id!("F1o3uGF7BP9tTvNXEPLFHtynamJfwpFkBAwsds1nEx8p");
-mod error;
-mod events;
+pub mod constants;
+pub mod error;
+pub mod events;
 mod helpers;
-mod types;
+pub mod pda;
+pub mod randomness;
+pub mod types;
 mod utils;
 
 #[program]
@@ -22,10 +38,23 @@ pub mod Raffle {
 
     use super::*;
     use crate::{
+        constants::{
+            DRAW_ALGORITHM_VERSION, ENTROPY_REVEAL_WINDOW_SECONDS, PAUSE_ALL, PAUSE_BUY_TICKETS,
+            PAUSE_CREATE_RAFFLE, PAUSE_DRAW_WINNER, PAUSE_PAYOUT, PAUSE_REQUEST_DRAW,
+        },
         error::RaffleError,
-        events::{RaffleCreated, TicketsBought},
-        helpers::get_unix_timestamp,
-        types::RaffleStatus,
+        events::{
+            DrawAbandoned, EntropyCommitted, EntropyRevealed, ProgramPaused, ProgramUnpaused,
+            RaffleClosed, RaffleCreated, RaffleSettled, RandomnessRerequested, RefundClaimed,
+            SalesClosed, ShippingExpired, TicketsBought, WinnerDrawn,
+        },
+        helpers::{
+            close_escrow, fresh_commit_slot, get_unix_timestamp, load_randomness, pay_out_seller,
+            transfer_from_escrow,
+        },
+        randomness::{check_draw_abandoned, entropy_commitment, provider_for},
+        types::{DeliveryTransition, RaffleStatus, RaffleTransition},
+        utils::bps_of,
     };
 
     pub fn initialise_counter(ctx: Context<InitializeCounter>) -> Result<()> {
@@ -33,6 +62,81 @@ pub mod Raffle {
         counter.counter = 0;
         Ok(())
     }
+
+    /// Creates the global `ProgramConfig`; the signer becomes its admin. Only
+    /// the program's upgrade authority may call it, so nobody can front-run
+    /// the deployer and take over fees, treasury, arbiter and pausing.
+    pub fn initialise_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.payment_mints = Vec::new();
+        config.paused = 0;
+        config.bump = ctx.bumps.config;
+        config.apply(params)?;
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        ctx.accounts.config.apply(params)?;
+        Ok(())
+    }
+
+    pub fn set_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.admin = new_admin;
+        Ok(())
+    }
+
+    /// Allows raffles to be priced in `payment_mint`, with platform fees for
+    /// that mint going to `treasury`.
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>) -> Result<()> {
+        let payment_mint = ctx.accounts.payment_mint.key();
+        let treasury = ctx.accounts.treasury.key();
+        ctx.accounts
+            .config
+            .allow_payment_mint(payment_mint, treasury)?;
+        Ok(())
+    }
+
+    /// Stops new raffles from using `mint`. Existing raffles are unaffected.
+    pub fn remove_payment_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+        ctx.accounts.config.remove_payment_mint(&mint)?;
+        Ok(())
+    }
+
+    /// Sets the `PAUSE_*` bits in `flags`. Refunds cannot be paused.
+    pub fn pause(ctx: Context<UpdateConfig>, flags: u16) -> Result<()> {
+        require!(
+            flags != 0 && flags & !PAUSE_ALL == 0,
+            RaffleError::InvalidPauseFlags
+        );
+        let config = &mut ctx.accounts.config;
+        config.paused |= flags;
+
+        emit!(ProgramPaused {
+            admin: config.admin,
+            flags,
+            paused: config.paused,
+        });
+        Ok(())
+    }
+
+    /// Clears the `PAUSE_*` bits in `flags`.
+    pub fn unpause(ctx: Context<UpdateConfig>, flags: u16) -> Result<()> {
+        require!(
+            flags != 0 && flags & !PAUSE_ALL == 0,
+            RaffleError::InvalidPauseFlags
+        );
+        let config = &mut ctx.accounts.config;
+        config.paused &= !flags;
+
+        emit!(ProgramUnpaused {
+            admin: config.admin,
+            flags,
+            paused: config.paused,
+        });
+        Ok(())
+    }
+    #[allow(clippy::too_many_arguments)]
     pub fn create_raffle(
         ctx: Context<CreateRaffle>,
         item_name: String,
@@ -43,14 +147,14 @@ pub mod Raffle {
         min_tickets: u32,
         max_tickets: u32,
         deadline: i64,
+        randomness_source: RandomnessSource,
     ) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_CREATE_RAFFLE)?;
         let raffle = &mut ctx.accounts.raffle_account;
         let clock = Clock::get()?;
         let counter = &mut ctx.accounts.counter;
 
         let raffle_id = counter.counter;
-        // Token decimals constant
-        const DECIMALS: u64 = 1_000_000; // 10^6 for 6 decimals
 
         // Input validation
         require!(selling_price > 0, RaffleError::InvalidPrice);
@@ -61,16 +165,45 @@ pub mod Raffle {
             deadline > clock.unix_timestamp,
             RaffleError::InvalidDeadline
         );
+        require!(
+            randomness_source != RandomnessSource::Deterministic
+                || cfg!(feature = "test-randomness"),
+            RaffleError::RandomnessSourceUnavailable
+        );
+        let treasury = ctx
+            .accounts
+            .config
+            .payment_mint(&ctx.accounts.payment_mint.key())
+            .ok_or(RaffleError::PaymentMintNotAllowed)?
+            .treasury;
 
-        // Convert prices to smallest unit (accounting for 6 decimals)
+        // Prices are given in whole tokens; convert them to the smallest unit
+        // of whichever allowed mint the raffle is paid in
+        let one_token = 10u64
+            .checked_pow(ctx.accounts.payment_mint.decimals.into())
+            .ok_or(RaffleError::Overflow)?;
         let selling_price_with_decimals = selling_price
-            .checked_mul(DECIMALS)
+            .checked_mul(one_token)
             .ok_or(RaffleError::Overflow)?;
 
         let ticket_price_with_decimals = ticket_price
-            .checked_mul(DECIMALS)
+            .checked_mul(one_token)
             .ok_or(RaffleError::Overflow)?;
 
+        // The seller's bond sits in escrow next to the ticket money until the
+        // raffle settles
+        let bond_amount = bps_of(selling_price_with_decimals, ctx.accounts.config.bond_bps)?;
+        if bond_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.escrow_payment_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, bond_amount)?;
+        }
+
         // Initialize raffle account
         raffle.seller = ctx.accounts.seller.key();
         raffle.payment_mint = ctx.accounts.payment_mint.key();
@@ -82,12 +215,16 @@ pub mod Raffle {
         raffle.min_tickets = min_tickets;
         raffle.max_tickets = max_tickets;
         raffle.deadline = deadline;
-        raffle.participants = Vec::new();
+        raffle.participant_count = 0;
+        raffle.current_page = 0;
         raffle.total_collected = 0;
+        raffle.total_refunded = 0;
         raffle.status = RaffleStatus::Active;
+        raffle.randomness_source = randomness_source;
         raffle.randomness_account = None;
         raffle.randomness_commit_slot = None;
         raffle.winner = None;
+        raffle.winning_ticket = None;
         raffle.claimed = false;
         raffle.bump = ctx.bumps.raffle_account;
         raffle.escrow_bump = ctx.bumps.escrow_payment_account;
@@ -97,8 +234,21 @@ pub mod Raffle {
         raffle.raffle_id = raffle_id;
         raffle.product_delivered_status = types::DeliveryStatus::Pending;
         raffle.tracking_info = None;
+        raffle.shipping_deadline = None;
         raffle.shipped_at = None;
         raffle.despute_deadline = None;
+        raffle.payout_released = false;
+        raffle.fee_bps = ctx.accounts.config.fee_bps;
+        raffle.treasury = treasury;
+        raffle.fee_collected = 0;
+        raffle.bond_amount = bond_amount;
+        raffle.bond_status = BondStatus::Held;
+        raffle.dispute_evidence_hash = None;
+        raffle.disputed_at = None;
+        raffle.dispute_resolution = None;
+        raffle.entropy = [0; 32];
+        raffle.entropy_commitments = 0;
+        raffle.entropy_reveals = 0;
         counter.counter = counter
             .counter
             .checked_add(1)
@@ -109,10 +259,13 @@ pub mod Raffle {
             seller: raffle.seller,
             ticket_price: ticket_price_with_decimals,
             deadline,
+            bond_amount,
         });
         Ok(())
     }
+    #[allow(clippy::bool_comparison)]
     pub fn buy_tickets(ctx: Context<BuyTickets>, num_tickets: u8) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_BUY_TICKETS)?;
         let raffle_account = &mut ctx.accounts.raffle_account;
         let clock = Clock::get()?.unix_timestamp;
         let escrow_account = &mut ctx.accounts.escrow_payment_account;
@@ -120,10 +273,6 @@ pub mod Raffle {
 
         let buyer_key = buyer.key();
 
-        require!(
-            raffle_account.participants.len() < 32,
-            RaffleError::RaffleFull
-        );
         // Basic validations
         require!(
             raffle_account.status == RaffleStatus::Active,
@@ -131,13 +280,11 @@ pub mod Raffle {
         );
         require!(num_tickets > 0, RaffleError::InvalidTicketCount);
 
-        // Only end the raffle and block purchases AFTER the deadline has passed.
-        // Previously this condition was inverted, which immediately marked the
-        // raffle as Ended while it was still active, causing RaffleNotActive errors.
-        if clock > raffle_account.deadline {
-            raffle_account.status = RaffleStatus::Ended;
-            return err!(RaffleError::DeadlinePassed);
-        }
+        // Sales stop at the deadline; the status moves on in close_sales
+        require!(
+            clock <= raffle_account.deadline,
+            RaffleError::DeadlinePassed
+        );
 
         require!(
             raffle_account.is_sold_out == false,
@@ -175,27 +322,62 @@ pub mod Raffle {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, total_price)?;
 
+        // Append the purchase to the participant log, opening the next page
+        // for the following buyer once this one fills up
+        let page = &mut ctx.accounts.participant_page;
+        if page.entries.is_empty() {
+            page.raffle = raffle_account.key();
+            page.page_index = raffle_account.current_page;
+            page.first_ticket = raffle_account.total_entries;
+            page.next_ticket = raffle_account.total_entries;
+            page.bump = ctx.bumps.participant_page;
+        }
+        page.record_purchase(buyer_key, num_tickets as u32)?;
+        if page.is_full() {
+            raffle_account.current_page = raffle_account
+                .current_page
+                .checked_add(1)
+                .ok_or(RaffleError::Overflow)?;
+        }
+
+        // Record the purchase on the buyer's receipt
+        let receipt = &mut ctx.accounts.ticket_receipt;
+        if receipt.tickets == 0 {
+            raffle_account.participant_count = raffle_account
+                .participant_count
+                .checked_add(1)
+                .ok_or(RaffleError::Overflow)?;
+            receipt.raffle = raffle_account.key();
+            receipt.buyer = buyer_key;
+            receipt.first_ticket = raffle_account.total_entries;
+            receipt.first_purchased_at = clock;
+            receipt.bump = ctx.bumps.ticket_receipt;
+        }
+        receipt.tickets = receipt
+            .tickets
+            .checked_add(num_tickets as u32)
+            .ok_or(RaffleError::Overflow)?;
+        receipt.total_paid = receipt
+            .total_paid
+            .checked_add(total_price)
+            .ok_or(RaffleError::Overflow)?;
+        receipt.last_purchased_at = clock;
+
         // Update total collected
         raffle_account.total_collected = raffle_account
             .total_collected
             .checked_add(total_price)
             .ok_or(RaffleError::Overflow)?;
 
-        // Add participants (each ticket = one entry)
-        if !raffle_account.participants.contains(&buyer_key) {
-            require!(
-                raffle_account.participants.len() < 32,
-                RaffleError::RaffleFull
-            );
-            raffle_account.participants.push(buyer_key);
-        }
         // FIX: Increment total_entries by num_tickets, not by 1
         raffle_account.total_entries = new_total_entries;
 
-        // Check if sold out based on TOTAL_ENTRIES
+        // Check if sold out based on TOTAL_ENTRIES. A sold out raffle is ready
+        // to draw: max_tickets >= min_tickets, so the minimum is met
         let max_tickets = raffle_account.max_tickets;
         if raffle_account.total_entries >= max_tickets as u64 {
             raffle_account.is_sold_out = true;
+            raffle_account.transition(RaffleTransition::CloseSales)?;
         }
         // Calculate progress
         let progress = RaffleAccount::calculate_progress(
@@ -204,22 +386,51 @@ pub mod Raffle {
         )?;
         // asigning the progress
         raffle_account.progress = progress;
-        //changing the raffle status if participants becomes greater than equal to 2
-        if raffle_account.participants.len() >= 2 {
-            raffle_account.status = RaffleStatus::Drawing;
-        }
         // Emit event
         emit!(TicketsBought {
             buyer: buyer_key,
             raffle: raffle_account.key(),
             number_of_tickets_bought: num_tickets,
             total_tickets_now: raffle_account.total_entries,
-            total_participants_now: raffle_account.participants.len() as u32
+            total_participants_now: raffle_account.participant_count
         });
         Ok(())
     }
    
+    /// Anyone can end ticket sales once the deadline has passed. The raffle
+    /// moves to `Drawing` if it reached `min_tickets`, otherwise to
+    /// `Refunding` (straight on to `Refunded` if nothing was collected).
+    pub fn close_sales(ctx: Context<CloseSales>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.status == RaffleStatus::Active,
+            RaffleError::RaffleNotActive
+        );
+        require!(clock > raffle.deadline, RaffleError::DeadlineNotReached);
+
+        if raffle.total_entries >= raffle.min_tickets as u64 {
+            raffle.transition(RaffleTransition::CloseSales)?;
+        } else {
+            raffle.transition(RaffleTransition::MissMinimum)?;
+            if raffle.total_collected == 0 {
+                raffle.transition(RaffleTransition::FinishRefunds)?;
+            }
+        }
+
+        emit!(SalesClosed {
+            raffle: raffle.key(),
+            total_entries: raffle.total_entries,
+            min_tickets: raffle.min_tickets,
+            status: raffle.status,
+            closed_at: clock,
+        });
+        Ok(())
+    }
+
     pub fn request_draw(ctx: Context<RequestDraw>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_REQUEST_DRAW)?;
         let raffle = &mut ctx.accounts.raffle_account;
         let clock = Clock::get()?;
 
@@ -232,277 +443,1287 @@ pub mod Raffle {
             raffle.status == RaffleStatus::Drawing,
             RaffleError::InvalidRaffleState
         );
-        require!(!raffle.participants.is_empty(), RaffleError::NoParticipants);
+        require!(raffle.participant_count > 0, RaffleError::NoParticipants);
+        require!(
+            raffle.total_entries >= raffle.min_tickets as u64,
+            RaffleError::MinTicketsNotReached
+        );
         require!(
             raffle.randomness_account.is_none(),
             RaffleError::RandomnessAlreadyRequested
         );
 
-        let randomness_data = RandomnessAccountData::parse(
-            ctx.accounts.randomness_account_data.data.borrow(),
-        )
-        .map_err(|_| RaffleError::InvalidRandomnessAccount)?;
-
-        require!(
-            randomness_data.seed_slot == clock.slot.saturating_sub(1),
-            RaffleError::RandomnessExpired
-        );
-        require!(
-            randomness_data.get_value(clock.slot).is_err(),
-            RaffleError::RandomnessAlreadyRevealed
-        );
+        let provider = provider_for(
+            raffle.randomness_source,
+            raffle.key(),
+            ctx.accounts.randomness_account_data.as_deref(),
+        )?;
+        let commitment = provider.commit(raffle, &clock)?;
 
-        raffle.randomness_account = Some(ctx.accounts.randomness_account_data.key());
-        raffle.randomness_commit_slot = Some(randomness_data.seed_slot);
+        raffle.randomness_account = Some(commitment.account);
+        raffle.randomness_commit_slot = Some(commitment.slot);
 
         msg!(
-            "Switchboard randomness committed for raffle {} at slot {}",
+            "{:?} randomness committed for raffle {} at slot {}",
+            raffle.randomness_source,
             raffle.key(),
-            randomness_data.seed_slot
+            commitment.slot
         );
 
         Ok(())
     }
 
-    pub fn draw_winner(ctx: Context<DrawWinner>) -> Result<()> {
+    /// Replaces a Switchboard commitment that can no longer be drawn from, so
+    /// a raffle cannot be stuck in `Drawing` by an oracle that never answers
+    /// or by a randomness account that was re-committed under it. After the
+    /// configured `randomness_timeout_slots` the old account must either be
+    /// unrevealed or hold a different commitment; a revealed value of the
+    /// stored commitment is drawn with `draw_winner` instead. Whoever holds
+    /// that account sees the reveal first and could withhold it, so only the
+    /// admin may replace it, otherwise a caller could discard an outcome they
+    /// dislike.
+    pub fn rerequest_draw(ctx: Context<RerequestDraw>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_REQUEST_DRAW)?;
         let raffle = &mut ctx.accounts.raffle_account;
         let clock = Clock::get()?;
-        let current_timestamp = get_unix_timestamp() as u64;
 
-        require!(
-            current_timestamp > raffle.deadline as u64,
-            RaffleError::DeadlineNotReached
-        );
         require!(!raffle.claimed, RaffleError::WinnerAlreadySelected);
         require!(
             raffle.status == RaffleStatus::Drawing,
             RaffleError::InvalidRaffleState
         );
-        require!(!raffle.participants.is_empty(), RaffleError::NoParticipants);
+        require!(
+            raffle.randomness_source == RandomnessSource::Switchboard,
+            RaffleError::WrongRandomnessSource
+        );
 
-        let stored_randomness_account = raffle
+        let abandoned_randomness_account = raffle
             .randomness_account
             .ok_or(RaffleError::RandomnessNotRequested)?;
-        let stored_commit_slot = raffle
+        let abandoned_commit_slot = raffle
             .randomness_commit_slot
             .ok_or(RaffleError::RandomnessNotRequested)?;
 
         require!(
-            ctx.accounts.randomness_account_data.key() == stored_randomness_account,
+            ctx.accounts.abandoned_randomness_account_data.key() == abandoned_randomness_account,
             RaffleError::InvalidRandomnessAccount
         );
+        require!(
+            clock.slot
+                > abandoned_commit_slot
+                    .saturating_add(ctx.accounts.config.randomness_timeout_slots),
+            RaffleError::RandomnessStillPending
+        );
+
+        {
+            let abandoned_data =
+                load_randomness(&ctx.accounts.abandoned_randomness_account_data)?;
+            // A re-committed account no longer holds the stored commitment, so
+            // its value can never be drawn. Otherwise a reveal, which always
+            // lands after the seed slot, leaves a drawable value behind
+            require!(
+                abandoned_data.seed_slot != abandoned_commit_slot
+                    || abandoned_data.reveal_slot <= abandoned_commit_slot,
+                RaffleError::RandomnessAlreadyRevealed
+            );
+        }
+
+        let commit_slot = fresh_commit_slot(&ctx.accounts.randomness_account_data, &clock)?;
+
+        raffle.randomness_account = Some(ctx.accounts.randomness_account_data.key());
+        raffle.randomness_commit_slot = Some(commit_slot);
+
+        emit!(RandomnessRerequested {
+            raffle: raffle.key(),
+            abandoned_randomness_account,
+            abandoned_commit_slot,
+            randomness_account: ctx.accounts.randomness_account_data.key(),
+            commit_slot,
+        });
+        Ok(())
+    }
 
-        let randomness_data = RandomnessAccountData::parse(
-            ctx.accounts.randomness_account_data.data.borrow(),
-        )
-        .map_err(|_| RaffleError::InvalidRandomnessAccount)?;
+    pub fn draw_winner(ctx: Context<DrawWinner>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_DRAW_WINNER)?;
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?;
+        let current_timestamp = get_unix_timestamp() as u64;
 
         require!(
-            randomness_data.seed_slot == stored_commit_slot,
-            RaffleError::RandomnessExpired
+            current_timestamp > raffle.deadline as u64,
+            RaffleError::DeadlineNotReached
+        );
+        require!(!raffle.claimed, RaffleError::WinnerAlreadySelected);
+        require!(
+            raffle.status == RaffleStatus::Drawing,
+            RaffleError::InvalidRaffleState
+        );
+        require!(raffle.participant_count > 0, RaffleError::NoParticipants);
+        require!(
+            raffle.total_entries >= raffle.min_tickets as u64,
+            RaffleError::MinTicketsNotReached
         );
 
-        let random_bytes = randomness_data
-            .get_value(clock.slot)
-            .map_err(|_| RaffleError::RandomnessNotResolved)?;
-        let random_number = u64::from_le_bytes(
-            random_bytes[..8]
-                .try_into()
-                .map_err(|_| RaffleError::InvalidRandomnessAccount)?,
+        require!(
+            raffle.randomness_account.is_some(),
+            RaffleError::RandomnessNotRequested
         );
-        let winner_index = (random_number % raffle.participants.len() as u64) as usize;
-        let winner = raffle.participants[winner_index];
+
+        let provider = provider_for(
+            raffle.randomness_source,
+            raffle.key(),
+            ctx.accounts.randomness_account_data.as_deref(),
+        )?;
+        let random_bytes = provider.reveal(raffle, &clock)?;
+        // Every ticket is an equal entry, so a wallet's odds scale with the
+        // number of tickets it bought. The caller passes the participant page
+        // that holds the winning ticket.
+        let winning_ticket =
+            RaffleAccount::pick_winning_ticket(&random_bytes, raffle.total_entries)?;
+        let winner = ctx
+            .accounts
+            .participant_page
+            .ticket_owner(winning_ticket)
+            .ok_or(RaffleError::WrongParticipantPage)?;
 
         raffle.winner = Some(winner);
+        raffle.winning_ticket = Some(winning_ticket);
         raffle.claimed = true;
-        raffle.status = RaffleStatus::Completed;
+        raffle.transition(RaffleTransition::DrawWinner)?;
+        raffle.shipping_deadline = Some(
+            clock
+                .unix_timestamp
+                .checked_add(ctx.accounts.config.shipping_window_seconds)
+                .ok_or(RaffleError::Overflow)?,
+        );
+
+        let draw_record = &mut ctx.accounts.draw_record;
+        draw_record.raffle = raffle.key();
+        draw_record.randomness_source = raffle.randomness_source;
+        draw_record.randomness_account = raffle
+            .randomness_account
+            .ok_or(RaffleError::RandomnessNotRequested)?;
+        draw_record.randomness = random_bytes;
+        draw_record.seed_slot = raffle
+            .randomness_commit_slot
+            .ok_or(RaffleError::RandomnessNotRequested)?;
+        draw_record.reveal_slot = clock.slot;
+        draw_record.total_entries = raffle.total_entries;
+        draw_record.winning_ticket = winning_ticket;
+        draw_record.winner = winner;
+        draw_record.participant_page = ctx.accounts.participant_page.page_index;
+        draw_record.algorithm_version = DRAW_ALGORITHM_VERSION;
+        draw_record.drawn_at = clock.unix_timestamp;
+        draw_record.bump = ctx.bumps.draw_record;
+
+        emit!(WinnerDrawn {
+            raffle: draw_record.raffle,
+            randomness_source: draw_record.randomness_source,
+            randomness_account: draw_record.randomness_account,
+            randomness: draw_record.randomness,
+            seed_slot: draw_record.seed_slot,
+            reveal_slot: draw_record.reveal_slot,
+            total_entries: draw_record.total_entries,
+            winning_ticket,
+            winner,
+            participant_page: draw_record.participant_page,
+            algorithm_version: DRAW_ALGORITHM_VERSION,
+        });
 
         msg!(
-            "Switchboard randomness resolved for raffle {} with seed slot {}",
+            "{:?} randomness resolved for raffle {} with commit slot {:?}",
+            raffle.randomness_source,
             raffle.key(),
-            randomness_data.seed_slot
+            raffle.randomness_commit_slot
         );
-        msg!("Winner index: {}", winner_index);
+        msg!("Winning ticket: {}", winning_ticket);
         msg!("Winner public key: {}", winner);
 
         Ok(())
     }
 
-    pub fn mark_shipped(ctx: Context<MarkShipped>, tracking_info: Option<String>) -> Result<()> {
-        let raffle_account = &mut ctx.accounts.raffle_account;
+    /// Commits a participant's secret for a `CommitReveal` raffle. Must happen
+    /// before the deadline so nobody can commit after seeing other reveals.
+    pub fn commit_entropy(ctx: Context<CommitEntropy>, commitment: [u8; 32]) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let receipt = &mut ctx.accounts.ticket_receipt;
         let clock = Clock::get()?.unix_timestamp;
-        raffle_account.tracking_info = tracking_info;
-        raffle_account.product_delivered_status = types::DeliveryStatus::Shipped;
-        raffle_account.shipped_at = Some(Clock::get()?.unix_timestamp);
 
-        raffle_account.despute_deadline = Some(clock + (30 * 24 * 60 * 60));
-        emit!(ProductShipped {
-            raffle: raffle_account.key(),
-            winner: raffle_account.winner.unwrap(),
-            shipped_at: Some(clock),
-        });
+        require!(
+            raffle.randomness_source == RandomnessSource::CommitReveal,
+            RaffleError::WrongRandomnessSource
+        );
+        require!(clock <= raffle.deadline, RaffleError::DeadlinePassed);
+        require!(
+            receipt.entropy_commitment.is_none(),
+            RaffleError::EntropyAlreadyCommitted
+        );
 
+        receipt.entropy_commitment = Some(commitment);
+        raffle.entropy_commitments = raffle
+            .entropy_commitments
+            .checked_add(1)
+            .ok_or(RaffleError::Overflow)?;
+
+        emit!(EntropyCommitted {
+            raffle: raffle.key(),
+            buyer: receipt.buyer,
+            commitment,
+        });
         Ok(())
     }
-    pub fn mark_delivered(
-        ctx: Context<MarkDelivered>,
-        tracking_info: Option<String>,
-    ) -> Result<()> {
+
+    /// Reveals a secret committed with `commit_entropy` and folds it into the
+    /// raffle's entropy. Only possible between the deadline and the draw
+    /// being requested.
+    pub fn reveal_entropy(ctx: Context<RevealEntropy>, secret: [u8; 32]) -> Result<()> {
         let raffle = &mut ctx.accounts.raffle_account;
+        let receipt = &mut ctx.accounts.ticket_receipt;
         let clock = Clock::get()?.unix_timestamp;
-        raffle.tracking_info = tracking_info;
-        raffle.product_delivered_status = types::DeliveryStatus::Delivered;
-        emit!(ProductDelivered {
+
+        require!(
+            raffle.randomness_source == RandomnessSource::CommitReveal,
+            RaffleError::WrongRandomnessSource
+        );
+        let reveal_deadline = raffle
+            .deadline
+            .saturating_add(ENTROPY_REVEAL_WINDOW_SECONDS);
+        require!(clock > raffle.deadline, RaffleError::DeadlineNotReached);
+        require!(clock <= reveal_deadline, RaffleError::RandomnessTooOld);
+        require!(
+            raffle.randomness_account.is_none(),
+            RaffleError::RandomnessAlreadyRequested
+        );
+        require!(!receipt.entropy_revealed, RaffleError::AlreadyClaimed);
+
+        let commitment = receipt
+            .entropy_commitment
+            .ok_or(RaffleError::InvalidEntropyReveal)?;
+        require!(
+            entropy_commitment(&raffle.key(), &receipt.buyer, &secret) == commitment,
+            RaffleError::InvalidEntropyReveal
+        );
+
+        for (byte, secret_byte) in raffle.entropy.iter_mut().zip(secret) {
+            *byte ^= secret_byte;
+        }
+        receipt.entropy_revealed = true;
+        raffle.entropy_reveals = raffle
+            .entropy_reveals
+            .checked_add(1)
+            .ok_or(RaffleError::Overflow)?;
+
+        emit!(EntropyRevealed {
             raffle: raffle.key(),
-            winner: raffle.winner.unwrap(),
-            delivered_at: Some(clock),
+            buyer: receipt.buyer,
+            reveals: raffle.entropy_reveals,
+            commitments: raffle.entropy_commitments,
         });
         Ok(())
     }
-}
-
-#[derive(Accounts)]
-#[instruction(
-    item_name: String,
-    item_description: String,
-    item_image_uri: String,
-    selling_price: u64,
-    ticket_price: u64,
-    min_tickets: u32,
-    max_tickets: u32,
-    deadline: i64,
-)]
-pub struct CreateRaffle<'info> {
-    #[account(mut)]
-    pub seller: Signer<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"global-counter"],
-        bump
-    )]
-    pub counter: Account<'info, Counter>,
-    /// Payment token mint (USDC, SOL wrapped, etc.)
-    pub payment_mint: Account<'info, token::Mint>,
+    /// Anyone can send a commit-reveal raffle to refunds once its reveal
+    /// window has closed without a single reveal, since `request_draw` can
+    /// then never succeed. Buyers claim with `claim_refund` and the seller
+    /// gets the bond back with `reclaim_bond`.
+    pub fn abandon_draw(ctx: Context<AbandonDraw>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?;
 
-    /// Seller's token account
-    #[account(
-        mut,
-        constraint = seller_token_account.mint == payment_mint.key(),
-        constraint = seller_token_account.owner == seller.key(),
-    )]
-    pub seller_token_account: Account<'info, token::TokenAccount>,
+        check_draw_abandoned(raffle, &clock)?;
+        raffle.transition(RaffleTransition::AbandonDraw)?;
 
-    /// Raffle PDA
-    #[account(
-        init,
-        payer = seller,
-        space = 8 + RaffleAccount::INIT_SPACE,
-        seeds = [
-            b"raffle",
-            seller.key().as_ref(),
-            &counter.counter.to_le_bytes()
-        ],
-        bump
-    )]
-    pub raffle_account: Account<'info, RaffleAccount>,
+        emit!(DrawAbandoned {
+            raffle: raffle.key(),
+            entropy_commitments: raffle.entropy_commitments,
+            abandoned_at: clock.unix_timestamp,
+        });
+        Ok(())
+    }
 
-    /// Escrow token account - THE FIX IS HERE
-    /// Must be initialized AFTER raffle_account exists
-    #[account(
-        init,
-        payer = seller,
-        seeds = [b"escrow_payment",seller.key().as_ref(), &counter.counter.to_le_bytes()],
-        bump,
-        token::mint = payment_mint,
-        token::authority = raffle_account,
-    )]
-    pub escrow_payment_account: Account<'info, token::TokenAccount>,
+    /// Returns a buyer's tickets at face value once the deadline has passed
+    /// without the raffle reaching `min_tickets`.
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+        let buyer_key = ctx.accounts.buyer.key();
 
-    /// Programs
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        // Refunding is entered either here, by the first claim on a raffle
+        // that missed min_tickets, or by a dispute resolved against the seller
+        if raffle.status != RaffleStatus::Refunding {
+            require!(clock > raffle.deadline, RaffleError::DeadlineNotReached);
+            require!(
+                raffle.total_entries < raffle.min_tickets as u64,
+                RaffleError::MinTicketsReached
+            );
+            raffle.transition(RaffleTransition::MissMinimum)?;
+        }
 
-#[derive(Accounts)]
-pub struct BuyTickets<'info> {
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+        let receipt = &mut ctx.accounts.ticket_receipt;
+        require!(!receipt.refund_claimed, RaffleError::AlreadyClaimed);
+        let tickets = receipt.tickets;
+        let amount = receipt.total_paid;
 
-    #[account(mut)]
-    pub buyer_token_accont: Account<'info, token::TokenAccount>,
+        transfer_from_escrow(
+            raffle,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.buyer_token_account,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
 
-    #[account(
-        mut,
-        constraint = raffle_account.participants.len() < 32 @ RaffleError::RaffleFull
-    )]
-    pub raffle_account: Account<'info, RaffleAccount>,
+        receipt.refund_claimed = true;
+        raffle.total_refunded = raffle
+            .total_refunded
+            .checked_add(amount)
+            .ok_or(RaffleError::Overflow)?;
+        if raffle.total_refunded >= raffle.total_collected {
+            raffle.transition(RaffleTransition::FinishRefunds)?;
+        }
 
-    #[account(
-        mut,
-        seeds = [b"escrow_payment",raffle_account.seller.key().as_ref(),&raffle_account.raffle_id.to_le_bytes()],
-        bump
-    )]
-    pub escrow_payment_account: Account<'info, token::TokenAccount>,
+        emit!(RefundClaimed {
+            raffle: raffle.key(),
+            buyer: buyer_key,
+            tickets,
+            amount,
+            total_refunded: raffle.total_refunded,
+        });
+        Ok(())
+    }
 
+    /// Returns the seller bond when the raffle failed through nobody's fault,
+    /// i.e. the deadline passed without reaching `min_tickets`.
+    pub fn reclaim_bond(ctx: Context<ReclaimBond>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_PAYOUT)?;
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.bond_status == BondStatus::Held,
+            RaffleError::BondNotHeld
+        );
+        // Refunds without a winner are never the seller's fault: the raffle
+        // missed min_tickets or its commit-reveal draw was abandoned
+        let refunding_without_winner = matches!(
+            raffle.status,
+            RaffleStatus::Refunding | RaffleStatus::Refunded
+        ) && raffle.winner.is_none();
+        require!(
+            (clock > raffle.deadline && raffle.total_entries < raffle.min_tickets as u64)
+                || refunding_without_winner,
+            RaffleError::BondNotReclaimable
+        );
+
+        transfer_from_escrow(
+            raffle,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.seller_token_account,
+            &ctx.accounts.token_program,
+            raffle.bond_amount,
+        )?;
+        raffle.bond_status = BondStatus::Returned;
+
+        emit!(BondReturned {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            amount: raffle.bond_amount,
+        });
+        Ok(())
+    }
+
+    /// Pays the escrowed pot out to the seller once the winner confirmed
+    /// delivery or the dispute window closed without a dispute. Everything
+    /// collected above `selling_price` is the seller's surplus. The platform
+    /// fee is split off to the treasury first; the seller bond comes back
+    /// fee-free on top.
+    pub fn release_to_seller(ctx: Context<ReleaseToSeller>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_PAYOUT)?;
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.status == RaffleStatus::Completed,
+            RaffleError::RaffleNotCompleted
+        );
+        if raffle.payout_released {
+            msg!("Payout for raffle {} was already released", raffle.key());
+            return Ok(());
+        }
+        raffle.check_payout_ready(clock)?;
+
+        let payout = pay_out_seller(
+            raffle,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.seller_token_account,
+            &ctx.accounts.treasury,
+            &ctx.accounts.token_program,
+        )?;
+
+        emit!(SellerPaid {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            amount: payout.amount,
+            fee: payout.fee,
+            bond_returned: payout.bond_returned,
+            treasury: raffle.treasury,
+            selling_price: raffle.selling_price,
+            surplus: payout.surplus,
+        });
+        Ok(())
+    }
+
+    /// Permissionless settlement crank for the keeper. Pays the seller exactly
+    /// like `release_to_seller` if that has not happened yet, then sweeps
+    /// anything left in escrow to the seller and closes it.
+    pub fn finalize_raffle(ctx: Context<FinalizeRaffle>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_PAYOUT)?;
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        raffle.check_payout_ready(clock)?;
+
+        if !raffle.payout_released {
+            let payout = pay_out_seller(
+                raffle,
+                &ctx.accounts.escrow_payment_account,
+                &ctx.accounts.seller_token_account,
+                &ctx.accounts.treasury,
+                &ctx.accounts.token_program,
+            )?;
+
+            emit!(SellerPaid {
+                raffle: raffle.key(),
+                seller: raffle.seller,
+                amount: payout.amount,
+                fee: payout.fee,
+                bond_returned: payout.bond_returned,
+                treasury: raffle.treasury,
+                selling_price: raffle.selling_price,
+                surplus: payout.surplus,
+            });
+        }
+
+        // Tokens sent to the escrow directly would otherwise block the close
+        ctx.accounts.escrow_payment_account.reload()?;
+        let leftover = ctx.accounts.escrow_payment_account.amount;
+        if leftover > 0 {
+            transfer_from_escrow(
+                raffle,
+                &ctx.accounts.escrow_payment_account,
+                &ctx.accounts.seller_token_account,
+                &ctx.accounts.token_program,
+                leftover,
+            )?;
+        }
+
+        close_escrow(
+            raffle,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )?;
+
+        emit!(RaffleSettled {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            total_collected: raffle.total_collected,
+            total_refunded: raffle.total_refunded,
+            fee: raffle.fee_collected,
+            leftover,
+            settled_at: clock,
+        });
+        Ok(())
+    }
+
+    /// Lets the seller withdraw a listing nobody has bought into yet. The bond
+    /// is returned, then the escrow token account and the raffle account are
+    /// closed and their rent goes back to the seller.
+    pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+
+        require!(
+            raffle.status == RaffleStatus::Active,
+            RaffleError::RaffleNotActive
+        );
+        require!(raffle.total_entries == 0, RaffleError::TicketsAlreadySold);
+
+        let bond_returned = if raffle.bond_status == BondStatus::Held {
+            raffle.bond_amount
+        } else {
+            0
+        };
+        raffle.transition(RaffleTransition::Cancel)?;
+        raffle.bond_status = BondStatus::Returned;
+
+        if bond_returned > 0 {
+            transfer_from_escrow(
+                raffle,
+                &ctx.accounts.escrow_payment_account,
+                &ctx.accounts.seller_token_account,
+                &ctx.accounts.token_program,
+                bond_returned,
+            )?;
+        }
+        close_escrow(
+            raffle,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )?;
+
+        if bond_returned > 0 {
+            emit!(BondReturned {
+                raffle: raffle.key(),
+                seller: raffle.seller,
+                amount: bond_returned,
+            });
+        }
+        emit!(RaffleCancelled {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            raffle_id: raffle.raffle_id,
+        });
+        Ok(())
+    }
+
+    /// Gives the seller back the rent of a raffle that is over: paid out or
+    /// fully refunded. Anything still in escrow (a bond nobody reclaimed,
+    /// stray deposits) goes to the seller before the escrow is closed, unless
+    /// `finalize_raffle` already closed it. With a `raffle_archive` account a
+    /// compact summary is kept on-chain. A cancelled raffle needs no closing:
+    /// `cancel_raffle` closes it right away.
+    pub fn close_raffle(ctx: Context<CloseRaffle>) -> Result<()> {
+        ctx.accounts.config.require_unpaused(PAUSE_PAYOUT)?;
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            (raffle.status == RaffleStatus::Completed && raffle.payout_released)
+                || raffle.status == RaffleStatus::Refunded,
+            RaffleError::RaffleNotSettled
+        );
+
+        // The escrow address is pinned by its seeds, so a seller cannot skip
+        // a funded escrow and strand its tokens with the raffle gone
+        let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+        if !escrow_payment_account.data_is_empty() {
+            let leftover =
+                TokenAccount::try_deserialize(&mut &escrow_payment_account.data.borrow()[..])?
+                    .amount;
+            if leftover > 0 {
+                transfer_from_escrow(
+                    raffle,
+                    escrow_payment_account,
+                    &ctx.accounts.seller_token_account,
+                    &ctx.accounts.token_program,
+                    leftover,
+                )?;
+                if raffle.bond_status == BondStatus::Held && leftover >= raffle.bond_amount {
+                    raffle.bond_status = BondStatus::Returned;
+                    emit!(BondReturned {
+                        raffle: raffle.key(),
+                        seller: raffle.seller,
+                        amount: raffle.bond_amount,
+                    });
+                }
+            }
+            close_escrow(
+                raffle,
+                escrow_payment_account,
+                &ctx.accounts.seller,
+                &ctx.accounts.token_program,
+            )?;
+        }
+
+        let archived = ctx.accounts.raffle_archive.is_some();
+        if let (Some(raffle_archive), Some(bump)) =
+            (&mut ctx.accounts.raffle_archive, ctx.bumps.raffle_archive)
+        {
+            raffle_archive.raffle = raffle.key();
+            raffle_archive.raffle_id = raffle.raffle_id;
+            raffle_archive.seller = raffle.seller;
+            raffle_archive.payment_mint = raffle.payment_mint;
+            raffle_archive.status = raffle.status;
+            raffle_archive.winner = raffle.winner;
+            raffle_archive.winning_ticket = raffle.winning_ticket;
+            raffle_archive.total_entries = raffle.total_entries;
+            raffle_archive.participant_count = raffle.participant_count;
+            raffle_archive.total_collected = raffle.total_collected;
+            raffle_archive.total_refunded = raffle.total_refunded;
+            raffle_archive.fee_collected = raffle.fee_collected;
+            raffle_archive.bond_amount = raffle.bond_amount;
+            raffle_archive.bond_status = raffle.bond_status;
+            raffle_archive.closed_at = clock;
+            raffle_archive.bump = bump;
+        }
+
+        emit!(RaffleClosed {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            raffle_id: raffle.raffle_id,
+            status: raffle.status,
+            archived,
+        });
+        Ok(())
+    }
+
+    /// Lets the winner contest a shipment before `despute_deadline`. Only a
+    /// hash of the reason/evidence is stored; the material itself lives
+    /// off-chain. Escrow stays locked until the arbiter resolves the dispute.
+    pub fn open_dispute(ctx: Context<OpenDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.status == RaffleStatus::Completed,
+            RaffleError::RaffleNotCompleted
+        );
+        require!(!raffle.payout_released, RaffleError::AlreadyClaimed);
+        require!(
+            matches!(
+                raffle.product_delivered_status,
+                types::DeliveryStatus::Shipped | types::DeliveryStatus::Delivered
+            ),
+            RaffleError::InvalidStatus
+        );
+        let despute_deadline = raffle.despute_deadline.ok_or(RaffleError::InvalidStatus)?;
+        require!(clock <= despute_deadline, RaffleError::DisputeWindowClosed);
+
+        raffle.transition_delivery(DeliveryTransition::OpenDispute)?;
+        raffle.dispute_evidence_hash = Some(evidence_hash);
+        raffle.disputed_at = Some(clock);
+
+        emit!(DisputeOpened {
+            raffle: raffle.key(),
+            winner: ctx.accounts.winner.key(),
+            evidence_hash,
+            opened_at: clock,
+        });
+        Ok(())
+    }
+
+    /// Settles an open dispute. `ReleaseToSeller` unlocks `release_to_seller`;
+    /// `RefundParticipants` moves the raffle to `Refunding` so every buyer can
+    /// get their tickets back through `claim_refund`, and slashes the seller
+    /// bond to the winner.
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+    ) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.product_delivered_status == types::DeliveryStatus::Disputed,
+            RaffleError::NoOpenDispute
+        );
+
+        raffle.transition_delivery(DeliveryTransition::ResolveDispute)?;
+        raffle.dispute_resolution = Some(resolution);
+        if resolution == DisputeResolution::RefundParticipants {
+            raffle.transition(RaffleTransition::SellerDefault)?;
+
+            if raffle.bond_status == BondStatus::Held {
+                transfer_from_escrow(
+                    raffle,
+                    &ctx.accounts.escrow_payment_account,
+                    &ctx.accounts.winner_token_account,
+                    &ctx.accounts.token_program,
+                    raffle.bond_amount,
+                )?;
+                raffle.bond_status = BondStatus::Slashed;
+
+                emit!(BondSlashed {
+                    raffle: raffle.key(),
+                    seller: raffle.seller,
+                    winner: ctx.accounts.winner_token_account.owner,
+                    amount: raffle.bond_amount,
+                });
+            }
+        }
+
+        emit!(DisputeResolved {
+            raffle: raffle.key(),
+            arbiter: ctx.accounts.arbiter.key(),
+            resolution,
+            resolved_at: clock,
+        });
+        Ok(())
+    }
+
+    /// Anyone can call this once the seller let `shipping_deadline` pass
+    /// without `mark_shipped`. Participants get their tickets back through
+    /// `claim_refund` and the seller bond is slashed to the winner.
+    pub fn expire_unshipped(ctx: Context<ExpireUnshipped>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+
+        require!(
+            raffle.status == RaffleStatus::Completed,
+            RaffleError::RaffleNotCompleted
+        );
+        require!(
+            raffle.product_delivered_status == types::DeliveryStatus::Pending,
+            RaffleError::InvalidStatus
+        );
+        let shipping_deadline = raffle.shipping_deadline.ok_or(RaffleError::InvalidStatus)?;
+        require!(
+            clock > shipping_deadline,
+            RaffleError::ShippingDeadlineNotReached
+        );
+
+        let bond_slashed = if raffle.bond_status == BondStatus::Held {
+            raffle.bond_amount
+        } else {
+            0
+        };
+        raffle.transition_delivery(DeliveryTransition::ExpireShipping)?;
+        raffle.transition(RaffleTransition::SellerDefault)?;
+
+        if bond_slashed > 0 {
+            transfer_from_escrow(
+                raffle,
+                &ctx.accounts.escrow_payment_account,
+                &ctx.accounts.winner_token_account,
+                &ctx.accounts.token_program,
+                bond_slashed,
+            )?;
+            raffle.bond_status = BondStatus::Slashed;
+
+            emit!(BondSlashed {
+                raffle: raffle.key(),
+                seller: raffle.seller,
+                winner: ctx.accounts.winner_token_account.owner,
+                amount: bond_slashed,
+            });
+        }
+
+        emit!(ShippingExpired {
+            raffle: raffle.key(),
+            seller: raffle.seller,
+            winner: ctx.accounts.winner_token_account.owner,
+            shipping_deadline,
+            bond_slashed,
+            expired_at: clock,
+        });
+        Ok(())
+    }
+
+    /// Seller marks the prize shipped before `shipping_deadline`, which opens
+    /// the dispute window. Only valid from `Pending`.
+    pub fn mark_shipped(ctx: Context<MarkShipped>, tracking_info: Option<String>) -> Result<()> {
+        let raffle_account = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+        let winner = raffle_account
+            .winner
+            .ok_or(RaffleError::RaffleNotCompleted)?;
+        require!(
+            raffle_account
+                .shipping_deadline
+                .is_none_or(|shipping_deadline| clock <= shipping_deadline),
+            RaffleError::ShippingDeadlinePassed
+        );
+        raffle_account.transition_delivery(DeliveryTransition::Ship)?;
+        raffle_account.tracking_info = tracking_info;
+        raffle_account.shipped_at = Some(Clock::get()?.unix_timestamp);
+
+        raffle_account.despute_deadline = Some(
+            clock
+                .checked_add(ctx.accounts.config.dispute_window_seconds)
+                .ok_or(RaffleError::Overflow)?,
+        );
+        emit!(ProductShipped {
+            raffle: raffle_account.key(),
+            winner,
+            shipped_at: Some(clock),
+        });
+
+        Ok(())
+    }
+
+    /// Winner confirms receipt. Only valid once the seller marked the prize
+    /// shipped.
+    pub fn mark_delivered(
+        ctx: Context<MarkDelivered>,
+        tracking_info: Option<String>,
+    ) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle_account;
+        let clock = Clock::get()?.unix_timestamp;
+        raffle.transition_delivery(DeliveryTransition::ConfirmDelivery)?;
+        raffle.tracking_info = tracking_info;
+        emit!(ProductDelivered {
+            raffle: raffle.key(),
+            winner: ctx.accounts.winner.key(),
+            delivered_at: Some(clock),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(
+    item_name: String,
+    item_description: String,
+    item_image_uri: String,
+    selling_price: u64,
+    ticket_price: u64,
+    min_tickets: u32,
+    max_tickets: u32,
+    deadline: i64,
+    randomness_source: RandomnessSource,
+)]
+pub struct CreateRaffle<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COUNTER_SEED],
+        bump
+    )]
+    pub counter: Account<'info, Counter>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Payment token mint (USDC, SOL wrapped, etc.)
+    pub payment_mint: Account<'info, token::Mint>,
+
+    /// Seller's token account
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == payment_mint.key(),
+        constraint = seller_token_account.owner == seller.key(),
+    )]
+    pub seller_token_account: Account<'info, token::TokenAccount>,
+
+    /// Raffle PDA
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + RaffleAccount::INIT_SPACE,
+        seeds = [
+            RAFFLE_SEED,
+            seller.key().as_ref(),
+            &counter.counter.to_le_bytes()
+        ],
+        bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    /// Escrow token account - THE FIX IS HERE
+    /// Must be initialized AFTER raffle_account exists
+    #[account(
+        init,
+        payer = seller,
+        seeds = [ESCROW_SEED,seller.key().as_ref(), &counter.counter.to_le_bytes()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = raffle_account,
+    )]
+    pub escrow_payment_account: Account<'info, token::TokenAccount>,
+
+    /// Programs
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTickets<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer_token_accont: Account<'info, token::TokenAccount>,
+
+    #[account(mut)]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.key().as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump
+    )]
+    pub escrow_payment_account: Account<'info, token::TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TicketReceipt::INIT_SPACE,
+        seeds = [TICKET_RECEIPT_SEED, raffle_account.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket_receipt: Account<'info, TicketReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + ParticipantPage::INIT_SPACE,
+        seeds = [
+            PARTICIPANT_PAGE_SEED,
+            raffle_account.key().as_ref(),
+            &raffle_account.current_page.to_le_bytes()
+        ],
+        bump
+    )]
+    pub participant_page: Account<'info, ParticipantPage>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+#[derive(Accounts)]
+pub struct CloseSales<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestDraw<'info> {
+    #[account(mut)]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Parsed and validated as a Switchboard randomness account in the handler.
+    /// Only required for Switchboard raffles.
+    pub randomness_account_data: Option<UncheckedAccount<'info>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RerequestDraw<'info> {
+    #[account(mut)]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Must match the commitment stored on the raffle; parsed in the handler.
+    pub abandoned_randomness_account_data: AccountInfo<'info>,
+
+    /// CHECK: Parsed and validated as a fresh Switchboard commitment in the handler.
+    pub randomness_account_data: AccountInfo<'info>,
+
+    #[account(constraint = authority.key() == config.admin @ RaffleError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawWinner<'info> {
+    #[account(mut)]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Must match the stored Switchboard randomness account and deserialize successfully.
+    /// Only required for Switchboard raffles.
+    pub randomness_account_data: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [
+            PARTICIPANT_PAGE_SEED,
+            raffle_account.key().as_ref(),
+            &participant_page.page_index.to_le_bytes()
+        ],
+        bump = participant_page.bump
+    )]
+    pub participant_page: Account<'info, ParticipantPage>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DrawRecord::INIT_SPACE,
+        seeds = [DRAW_RECORD_SEED, raffle_account.key().as_ref()],
+        bump
+    )]
+    pub draw_record: Account<'info, DrawRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct CommitEntropy<'info> {
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        has_one = buyer @ RaffleError::NotParticipant,
+        seeds = [TICKET_RECEIPT_SEED, raffle_account.key().as_ref(), buyer.key().as_ref()],
+        bump = ticket_receipt.bump
+    )]
+    pub ticket_receipt: Account<'info, TicketReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct RevealEntropy<'info> {
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        has_one = buyer @ RaffleError::NotParticipant,
+        seeds = [TICKET_RECEIPT_SEED, raffle_account.key().as_ref(), buyer.key().as_ref()],
+        bump = ticket_receipt.bump
+    )]
+    pub ticket_receipt: Account<'info, TicketReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct AbandonDraw<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    pub caller: Signer<'info>,
+}
+
 #[derive(Accounts)]
-pub struct RequestDraw<'info> {
+pub struct ClaimRefund<'info> {
     #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_token_account.mint == raffle_account.payment_mint,
+        constraint = buyer_token_account.owner == buyer.key(),
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
     pub raffle_account: Account<'info, RaffleAccount>,
 
-    /// CHECK: Parsed and validated as a Switchboard randomness account in the handler.
-    pub randomness_account_data: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
 
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = buyer @ RaffleError::NotParticipant,
+        seeds = [TICKET_RECEIPT_SEED, raffle_account.key().as_ref(), buyer.key().as_ref()],
+        bump = ticket_receipt.bump
+    )]
+    pub ticket_receipt: Account<'info, TicketReceipt>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct DrawWinner<'info> {
-    #[account(mut)]
+pub struct ReleaseToSeller<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == raffle_account.payment_mint,
+        constraint = seller_token_account.owner == seller.key(),
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = raffle_account.treasury @ RaffleError::InvalidTreasury
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = seller @ RaffleError::NotSeller,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
     pub raffle_account: Account<'info, RaffleAccount>,
 
-    /// CHECK: Must match the stored Switchboard randomness account and deserialize successfully.
-    pub randomness_account_data: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct MarkShipped<'info> {
+pub struct FinalizeRaffle<'info> {
+    pub caller: Signer<'info>,
+
+    /// CHECK: Only receives the escrow rent; pinned to the raffle's seller.
+    #[account(mut, address = raffle_account.seller @ RaffleError::NotSeller)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == raffle_account.payment_mint,
+        constraint = seller_token_account.owner == raffle_account.seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = raffle_account.treasury @ RaffleError::InvalidTreasury
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRaffle<'info> {
     #[account(mut)]
-    pub seller: Account<'info, TokenAccount>,
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == raffle_account.payment_mint,
+        constraint = seller_token_account.owner == seller.key(),
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds=[b"raffle_account",
-        raffle_account.seller.key().as_ref(),
-        &counter.counter.to_le_bytes()
+        has_one = seller @ RaffleError::NotSeller,
+        close = seller,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
         ],
         bump = raffle_account.bump
     )]
     pub raffle_account: Account<'info, RaffleAccount>,
 
+    /// Empty once `finalize_raffle` closed it
+    /// CHECK: Pinned to the escrow PDA by its seeds; parsed in the handler.
     #[account(
         mut,
-        seeds = [b"global-counter"],
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + RaffleArchive::INIT_SPACE,
+        seeds = [RAFFLE_ARCHIVE_SEED, raffle_account.key().as_ref()],
         bump
     )]
-    pub counter: Account<'info, Counter>,
+    pub raffle_archive: Option<Account<'info, RaffleArchive>>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct MarkDelivered<'info> {
+pub struct CancelRaffle<'info> {
     #[account(mut)]
-    pub buyer: Account<'info, TokenAccount>,
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == raffle_account.payment_mint,
+        constraint = seller_token_account.owner == seller.key(),
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds=[b"raffle_account",
-        raffle_account.seller.key().as_ref(),
-        &counter.counter.to_le_bytes()
+        has_one = seller @ RaffleError::NotSeller,
+        close = seller,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
         ],
         bump = raffle_account.bump
     )]
@@ -510,10 +1731,175 @@ pub struct MarkDelivered<'info> {
 
     #[account(
         mut,
-        seeds = [b"global-counter"],
-        bump
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
     )]
-    pub counter: Account<'info, Counter>,
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = raffle_account.winner == Some(winner.key()) @ RaffleError::NotWinner,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = arbiter @ RaffleError::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    /// Receives the slashed bond when the dispute goes against the seller
+    #[account(
+        mut,
+        constraint = winner_token_account.mint == raffle_account.payment_mint,
+        constraint = Some(winner_token_account.owner) == raffle_account.winner @ RaffleError::NotWinner,
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireUnshipped<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    /// Receives the slashed bond as compensation
+    #[account(
+        mut,
+        constraint = winner_token_account.mint == raffle_account.payment_mint,
+        constraint = Some(winner_token_account.owner) == raffle_account.winner @ RaffleError::NotWinner,
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBond<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == raffle_account.payment_mint,
+        constraint = seller_token_account.owner == seller.key(),
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = seller @ RaffleError::NotSeller,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED,raffle_account.seller.as_ref(),&raffle_account.raffle_id.to_le_bytes()],
+        bump = raffle_account.escrow_bump
+    )]
+    pub escrow_payment_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MarkShipped<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        has_one = seller @ RaffleError::NotSeller,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
+}
+
+#[derive(Accounts)]
+pub struct MarkDelivered<'info> {
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = raffle_account.winner == Some(winner.key()) @ RaffleError::NotWinner,
+        seeds = [
+            RAFFLE_SEED,
+            raffle_account.seller.as_ref(),
+            &raffle_account.raffle_id.to_le_bytes()
+        ],
+        bump = raffle_account.bump
+    )]
+    pub raffle_account: Account<'info, RaffleAccount>,
 }
 
 #[derive(Accounts)]
@@ -522,7 +1908,7 @@ pub struct InitializeCounter<'info> {
         init,
         payer = signer,
         space = 8 + types::Counter::INIT_SPACE,
-        seeds = [b"global-counter"],
+        seeds = [COUNTER_SEED],
         bump
     )]
     pub counter: Account<'info, types::Counter>,
@@ -531,3 +1917,59 @@ pub struct InitializeCounter<'info> {
 
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Raffle>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ RaffleError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ RaffleError::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ RaffleError::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+
+    pub payment_mint: Account<'info, token::Mint>,
+
+    #[account(
+        constraint = treasury.mint == payment_mint.key() @ RaffleError::InvalidTreasury
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+}
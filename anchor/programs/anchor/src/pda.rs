@@ -0,0 +1,69 @@
+//! Seeds of every PDA the program owns, and helpers deriving their addresses.
+//! Off-chain code should use these (through the `cpi`/`no-entrypoint` build)
+//! instead of repeating the seed strings.
+
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+
+pub const COUNTER_SEED: &[u8] = b"global-counter";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const RAFFLE_SEED: &[u8] = b"raffle";
+pub const ESCROW_SEED: &[u8] = b"escrow_payment";
+pub const TICKET_RECEIPT_SEED: &[u8] = b"ticket_receipt";
+pub const PARTICIPANT_PAGE_SEED: &[u8] = b"participant_page";
+pub const DRAW_RECORD_SEED: &[u8] = b"draw_record";
+pub const RAFFLE_ARCHIVE_SEED: &[u8] = b"raffle_archive";
+
+pub fn find_counter_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COUNTER_SEED], &crate::ID)
+}
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::ID)
+}
+
+/// The program's `ProgramData` account, owned by the upgradeable loader. Its
+/// upgrade authority is the only signer `initialise_config` accepts.
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[crate::ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// `raffle_id` is the value of the global counter when the raffle was created.
+pub fn find_raffle_address(seller: &Pubkey, raffle_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RAFFLE_SEED, seller.as_ref(), &raffle_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_escrow_address(seller: &Pubkey, raffle_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ESCROW_SEED, seller.as_ref(), &raffle_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_ticket_receipt_address(raffle: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TICKET_RECEIPT_SEED, raffle.as_ref(), buyer.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_participant_page_address(raffle: &Pubkey, page_index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PARTICIPANT_PAGE_SEED,
+            raffle.as_ref(),
+            &page_index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_draw_record_address(raffle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DRAW_RECORD_SEED, raffle.as_ref()], &crate::ID)
+}
+
+pub fn find_raffle_archive_address(raffle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RAFFLE_ARCHIVE_SEED, raffle.as_ref()], &crate::ID)
+}
@@ -0,0 +1,371 @@
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    constants::ENTROPY_REVEAL_WINDOW_SECONDS,
+    error::RaffleError,
+    helpers::{fresh_commit_slot, load_randomness},
+    types::{RaffleAccount, RandomnessSource},
+};
+
+/// What a provider locked in during `request_draw`. Stored on the raffle as
+/// `randomness_account` and `randomness_commit_slot`.
+pub struct Commitment {
+    pub account: Pubkey,
+    pub slot: u64,
+}
+
+/// Source of the 32 bytes `draw_winner` turns into a winning ticket.
+///
+/// `commit` runs in `request_draw` and has to fix the outcome before anybody
+/// can know it. `reveal` runs in `draw_winner` and returns the value for the
+/// commitment stored on the raffle.
+pub trait RandomnessProvider {
+    fn commit(&self, raffle: &RaffleAccount, clock: &Clock) -> Result<Commitment>;
+    fn reveal(&self, raffle: &RaffleAccount, clock: &Clock) -> Result<[u8; 32]>;
+}
+
+/// Picks the provider recorded on the raffle at `create_raffle`.
+/// `randomness_account_data` is only used by Switchboard.
+pub fn provider_for<'a, 'info>(
+    source: RandomnessSource,
+    raffle: Pubkey,
+    randomness_account_data: Option<&'a AccountInfo<'info>>,
+) -> Result<Box<dyn RandomnessProvider + 'a>> {
+    match source {
+        RandomnessSource::Switchboard => {
+            let randomness_account_data =
+                randomness_account_data.ok_or(RaffleError::InvalidRandomnessAccount)?;
+            Ok(Box::new(SwitchboardProvider {
+                randomness_account_data,
+            }))
+        }
+        RandomnessSource::CommitReveal => Ok(Box::new(CommitRevealProvider { raffle })),
+        #[cfg(feature = "test-randomness")]
+        RandomnessSource::Deterministic => Ok(Box::new(DeterministicProvider { raffle })),
+        #[cfg(not(feature = "test-randomness"))]
+        RandomnessSource::Deterministic => err!(RaffleError::RandomnessSourceUnavailable),
+    }
+}
+
+/// Switchboard on-demand randomness: commit to an account seeded in the
+/// previous slot, consume the oracle's value in the slot it is revealed.
+pub struct SwitchboardProvider<'a, 'info> {
+    pub randomness_account_data: &'a AccountInfo<'info>,
+}
+
+impl RandomnessProvider for SwitchboardProvider<'_, '_> {
+    fn commit(&self, _raffle: &RaffleAccount, clock: &Clock) -> Result<Commitment> {
+        let slot = fresh_commit_slot(self.randomness_account_data, clock)?;
+        Ok(Commitment {
+            account: self.randomness_account_data.key(),
+            slot,
+        })
+    }
+
+    fn reveal(&self, raffle: &RaffleAccount, clock: &Clock) -> Result<[u8; 32]> {
+        let stored_randomness_account = raffle
+            .randomness_account
+            .ok_or(RaffleError::RandomnessNotRequested)?;
+        let stored_commit_slot = raffle
+            .randomness_commit_slot
+            .ok_or(RaffleError::RandomnessNotRequested)?;
+
+        require!(
+            self.randomness_account_data.key() == stored_randomness_account,
+            RaffleError::InvalidRandomnessAccount
+        );
+
+        let randomness_data = load_randomness(self.randomness_account_data)?;
+        require!(
+            randomness_data.seed_slot == stored_commit_slot,
+            RaffleError::RandomnessExpired
+        );
+
+        let value = randomness_data
+            .get_value(clock.slot)
+            .map_err(|_| RaffleError::RandomnessNotResolved)?;
+        Ok(value)
+    }
+}
+
+/// Entropy contributed by the participants themselves, for small private
+/// raffles that do not want an oracle. Buyers commit to
+/// `entropy_commitment(raffle, buyer, secret)` before the deadline and reveal
+/// the secret afterwards; the revealed secrets are XORed into
+/// `RaffleAccount::entropy`.
+///
+/// The draw can be committed once every commitment is revealed, or once
+/// `ENTROPY_REVEAL_WINDOW_SECONDS` have passed, so a single participant
+/// cannot stall it by withholding a reveal. Withholding still biases it: the
+/// last participant to reveal already knows every other secret, so they can
+/// compute the outcome with and without their own and keep whichever they
+/// prefer. Use Switchboard when that matters. If nobody reveals at all,
+/// `abandon_draw` sends the raffle to refunds; see `check_draw_abandoned`.
+pub struct CommitRevealProvider {
+    pub raffle: Pubkey,
+}
+
+/// End of the window in which participants of a commit-reveal raffle reveal
+/// their secrets.
+pub fn reveal_deadline(raffle: &RaffleAccount) -> Result<i64> {
+    Ok(raffle
+        .deadline
+        .checked_add(ENTROPY_REVEAL_WINDOW_SECONDS)
+        .ok_or(RaffleError::Overflow)?)
+}
+
+/// Checks a commit-reveal draw can never happen: the reveal window closed
+/// without a single reveal, so `request_draw` would fail forever.
+pub fn check_draw_abandoned(raffle: &RaffleAccount, clock: &Clock) -> Result<()> {
+    require!(
+        raffle.randomness_source == RandomnessSource::CommitReveal,
+        RaffleError::WrongRandomnessSource
+    );
+    require!(
+        clock.unix_timestamp > reveal_deadline(raffle)?,
+        RaffleError::RevealWindowOpen
+    );
+    require!(raffle.entropy_reveals == 0, RaffleError::EntropyWasRevealed);
+    Ok(())
+}
+
+impl RandomnessProvider for CommitRevealProvider {
+    fn commit(&self, raffle: &RaffleAccount, clock: &Clock) -> Result<Commitment> {
+        require!(raffle.entropy_reveals > 0, RaffleError::NoEntropyRevealed);
+        require!(
+            raffle.entropy_reveals == raffle.entropy_commitments
+                || clock.unix_timestamp > reveal_deadline(raffle)?,
+            RaffleError::RevealWindowOpen
+        );
+
+        Ok(Commitment {
+            account: self.raffle,
+            slot: clock.slot,
+        })
+    }
+
+    fn reveal(&self, raffle: &RaffleAccount, _clock: &Clock) -> Result<[u8; 32]> {
+        require!(
+            raffle.randomness_account == Some(self.raffle),
+            RaffleError::RandomnessNotRequested
+        );
+
+        let mut hasher = Sha256::new();
+        hasher.update(raffle.entropy);
+        hasher.update(self.raffle.as_ref());
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Predictable randomness derived from the raffle and commit slot, so the
+/// whole draw path runs in local tests without an oracle. Never build a
+/// deployable program with the `test-randomness` feature.
+#[cfg(feature = "test-randomness")]
+pub struct DeterministicProvider {
+    pub raffle: Pubkey,
+}
+
+#[cfg(feature = "test-randomness")]
+impl RandomnessProvider for DeterministicProvider {
+    fn commit(&self, _raffle: &RaffleAccount, clock: &Clock) -> Result<Commitment> {
+        Ok(Commitment {
+            account: self.raffle,
+            slot: clock.slot,
+        })
+    }
+
+    fn reveal(&self, raffle: &RaffleAccount, _clock: &Clock) -> Result<[u8; 32]> {
+        let commit_slot = raffle
+            .randomness_commit_slot
+            .ok_or(RaffleError::RandomnessNotRequested)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.raffle.as_ref());
+        hasher.update(commit_slot.to_le_bytes());
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Hash a participant commits to in `commit_entropy`. Binding the raffle and
+/// buyer stops one participant from replaying another's commitment to cancel
+/// out their secret.
+pub fn entropy_commitment(raffle: &Pubkey, buyer: &Pubkey, secret: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(raffle.as_ref());
+    hasher.update(buyer.as_ref());
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RaffleStatus;
+    use switchboard_on_demand::{accounts::RandomnessAccountData, Discriminator, Owner};
+
+    const DEADLINE: i64 = 1_000_000;
+
+    fn commit_reveal_raffle(commitments: u32, reveals: u32) -> RaffleAccount {
+        let zeroed = vec![0u8; RaffleAccount::INIT_SPACE];
+        let mut raffle = RaffleAccount::deserialize(&mut &zeroed[..]).unwrap();
+        raffle.status = RaffleStatus::Drawing;
+        raffle.randomness_source = RandomnessSource::CommitReveal;
+        raffle.deadline = DEADLINE;
+        raffle.entropy_commitments = commitments;
+        raffle.entropy_reveals = reveals;
+        raffle
+    }
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock {
+            slot: 500,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    /// Switchboard randomness account data committed at `seed_slot`.
+    fn randomness_data(seed_slot: u64) -> Vec<u8> {
+        let mut data = RandomnessAccountData::DISCRIMINATOR.to_vec();
+        data.resize(RandomnessAccountData::size(), 0);
+        // authority, queue and seed_slothash come first
+        data[8 + 96..8 + 104].copy_from_slice(&seed_slot.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn entropy_commitment_binds_raffle_buyer_and_secret() {
+        let raffle = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let secret = [9u8; 32];
+        let commitment = entropy_commitment(&raffle, &buyer, &secret);
+
+        assert_eq!(entropy_commitment(&raffle, &buyer, &secret), commitment);
+        assert_ne!(entropy_commitment(&raffle, &buyer, &[8u8; 32]), commitment);
+        assert_ne!(
+            entropy_commitment(&raffle, &Pubkey::new_unique(), &secret),
+            commitment
+        );
+        assert_ne!(
+            entropy_commitment(&Pubkey::new_unique(), &buyer, &secret),
+            commitment
+        );
+    }
+
+    #[test]
+    fn commit_waits_for_missing_reveals_until_the_window_closes() {
+        let provider = CommitRevealProvider {
+            raffle: Pubkey::new_unique(),
+        };
+        let window_end = DEADLINE + ENTROPY_REVEAL_WINDOW_SECONDS;
+
+        let partly_revealed = commit_reveal_raffle(2, 1);
+        assert_eq!(
+            provider
+                .commit(&partly_revealed, &clock_at(window_end))
+                .err(),
+            Some(RaffleError::RevealWindowOpen.into())
+        );
+        let commitment = provider
+            .commit(&partly_revealed, &clock_at(window_end + 1))
+            .unwrap();
+        assert_eq!(commitment.account, provider.raffle);
+        assert_eq!(commitment.slot, 500);
+
+        let fully_revealed = commit_reveal_raffle(2, 2);
+        assert!(provider
+            .commit(&fully_revealed, &clock_at(DEADLINE + 1))
+            .is_ok());
+    }
+
+    #[test]
+    fn commit_needs_at_least_one_reveal() {
+        let provider = CommitRevealProvider {
+            raffle: Pubkey::new_unique(),
+        };
+        let raffle = commit_reveal_raffle(3, 0);
+        let after_window = clock_at(DEADLINE + ENTROPY_REVEAL_WINDOW_SECONDS + 1);
+        assert_eq!(
+            provider.commit(&raffle, &after_window).err(),
+            Some(RaffleError::NoEntropyRevealed.into())
+        );
+    }
+
+    #[test]
+    fn draw_is_abandoned_only_after_a_window_without_reveals() {
+        let window_end = DEADLINE + ENTROPY_REVEAL_WINDOW_SECONDS;
+        let unrevealed = commit_reveal_raffle(3, 0);
+
+        assert_eq!(
+            check_draw_abandoned(&unrevealed, &clock_at(window_end)).err(),
+            Some(RaffleError::RevealWindowOpen.into())
+        );
+        assert!(check_draw_abandoned(&unrevealed, &clock_at(window_end + 1)).is_ok());
+        // Nobody committed either: still abandoned
+        assert!(check_draw_abandoned(&commit_reveal_raffle(0, 0), &clock_at(window_end + 1)).is_ok());
+
+        assert_eq!(
+            check_draw_abandoned(&commit_reveal_raffle(3, 1), &clock_at(window_end + 1)).err(),
+            Some(RaffleError::EntropyWasRevealed.into())
+        );
+
+        let mut switchboard = commit_reveal_raffle(0, 0);
+        switchboard.randomness_source = RandomnessSource::Switchboard;
+        assert_eq!(
+            check_draw_abandoned(&switchboard, &clock_at(window_end + 1)).err(),
+            Some(RaffleError::WrongRandomnessSource.into())
+        );
+    }
+
+    #[test]
+    fn commit_reveal_value_depends_on_the_revealed_entropy() {
+        let provider = CommitRevealProvider {
+            raffle: Pubkey::new_unique(),
+        };
+        let mut raffle = commit_reveal_raffle(2, 2);
+        let clock = clock_at(DEADLINE + 1);
+        assert_eq!(
+            provider.reveal(&raffle, &clock).err(),
+            Some(RaffleError::RandomnessNotRequested.into())
+        );
+
+        raffle.randomness_account = Some(provider.raffle);
+        let value = provider.reveal(&raffle, &clock).unwrap();
+        assert_eq!(provider.reveal(&raffle, &clock).unwrap(), value);
+        raffle.entropy[0] ^= 1;
+        assert_ne!(provider.reveal(&raffle, &clock).unwrap(), value);
+    }
+
+    #[test]
+    fn switchboard_rejects_randomness_accounts_it_does_not_own() {
+        let key = Pubkey::new_unique();
+        let clock = clock_at(DEADLINE + 1);
+        let raffle = commit_reveal_raffle(0, 0);
+        let switchboard = RandomnessAccountData::owner();
+        let forger = Pubkey::new_unique();
+
+        for (owner, expected) in [
+            (&forger, Err(RaffleError::InvalidRandomnessAccount.into())),
+            (&switchboard, Ok(clock.slot - 1)),
+        ] {
+            let mut lamports = 1_000_000;
+            let mut data = randomness_data(clock.slot - 1);
+            let info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                owner,
+                false,
+                0,
+            );
+            let provider = SwitchboardProvider {
+                randomness_account_data: &info,
+            };
+            let commitment = provider.commit(&raffle, &clock).map(|c| c.slot);
+            assert_eq!(commitment, expected, "owner {owner}");
+        }
+    }
+}
@@ -20,21 +20,37 @@ pub struct RaffleAccount {
     pub min_tickets: u32,
     pub max_tickets: u32,
     pub deadline: i64,
-    #[max_len(32)]
-    pub participants: Vec<Pubkey>,
+    pub participant_count: u32, // Distinct buyers
+    pub current_page: u32,      // ParticipantPage that the next purchase is appended to
     pub total_collected: u64,
+    pub total_refunded: u64,
     pub status: RaffleStatus,
+    pub randomness_source: RandomnessSource,
     pub randomness_account: Option<Pubkey>,
     pub randomness_commit_slot: Option<u64>,
     pub winner: Option<Pubkey>,
+    pub winning_ticket: Option<u64>, // Index in 0..total_entries that won the draw
     pub claimed: bool,
     pub bump: u8,
     pub escrow_bump: u8,
     pub product_delivered_status:DeliveryStatus,
     #[max_len(32)]
     pub tracking_info: Option<String>,
+    pub shipping_deadline: Option<i64>, // Set at the draw; see expire_unshipped
     pub shipped_at:Option<i64>,
-    pub despute_deadline:Option<i64>
+    pub despute_deadline:Option<i64>,
+    pub payout_released: bool,
+    pub fee_bps: u16,     // Platform fee snapshotted from ProgramConfig at creation
+    pub treasury: Pubkey, // Token account receiving the platform fee
+    pub fee_collected: u64,
+    pub bond_amount: u64, // Locked by the seller at creation
+    pub bond_status: BondStatus,
+    pub dispute_evidence_hash: Option<[u8; 32]>,
+    pub disputed_at: Option<i64>,
+    pub dispute_resolution: Option<DisputeResolution>,
+    pub entropy: [u8; 32],        // XOR of revealed participant secrets (CommitReveal only)
+    pub entropy_commitments: u32, // Participants that committed a secret
+    pub entropy_reveals: u32,     // Participants that revealed their secret
 }
 
 #[account]
@@ -43,13 +59,134 @@ pub struct Counter {
     pub counter:u64
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq,Debug,InitSpace)]
+/// One per buyer per raffle, created on the first `buy_tickets` and topped up
+/// on every later purchase.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TicketReceipt {
+    pub raffle: Pubkey,
+    pub buyer: Pubkey,
+    pub tickets: u32,
+    pub total_paid: u64,
+    pub first_ticket: u64, // Sales-order number of the buyer's first ticket
+    pub first_purchased_at: i64,
+    pub last_purchased_at: i64,
+    pub refund_claimed: bool,
+    pub entropy_commitment: Option<[u8; 32]>,
+    pub entropy_revealed: bool,
+    pub bump: u8,
+}
+
+/// Append-only log of purchases for a raffle, split across PDAs seeded by
+/// `page_index` so the number of buyers is bounded only by `max_tickets`.
+/// Page `n + 1` starts at the ticket where page `n` ends.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ParticipantPage {
+    pub raffle: Pubkey,
+    pub page_index: u32,
+    pub first_ticket: u64, // First ticket number recorded on this page
+    pub next_ticket: u64,  // One past the last ticket recorded on this page
+    #[max_len(48)]
+    pub entries: Vec<TicketRange>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct TicketRange {
+    pub buyer: Pubkey,
+    pub first_ticket: u64,
+    pub tickets: u32,
+}
+
+/// Everything needed to replay a draw: feed `randomness` and `total_entries`
+/// to the algorithm named by `algorithm_version`, then look the ticket up in
+/// the raffle's participant pages.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct DrawRecord {
+    pub raffle: Pubkey,
+    pub randomness_source: RandomnessSource,
+    pub randomness_account: Pubkey,
+    pub randomness: [u8; 32], // Raw revealed bytes
+    pub seed_slot: u64,       // Slot the randomness was committed at
+    pub reveal_slot: u64,     // Slot the draw consumed the reveal in
+    pub total_entries: u64,
+    pub winning_ticket: u64,
+    pub winner: Pubkey,
+    pub participant_page: u32,
+    pub algorithm_version: u8,
+    pub drawn_at: i64,
+    pub bump: u8,
+}
+
+/// Compact summary left behind by `close_raffle` when the seller asks for
+/// one, so the history survives the raffle account being closed.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RaffleArchive {
+    pub raffle: Pubkey,
+    pub raffle_id: u64,
+    pub seller: Pubkey,
+    pub payment_mint: Pubkey,
+    pub status: RaffleStatus,
+    pub winner: Option<Pubkey>,
+    pub winning_ticket: Option<u64>,
+    pub total_entries: u64,
+    pub participant_count: u32,
+    pub total_collected: u64,
+    pub total_refunded: u64,
+    pub fee_collected: u64,
+    pub bond_amount: u64,
+    pub bond_status: BondStatus,
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+/// Global settings, initialised once. Every instruction that moves money
+/// reads its parameters from here.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,   // Can update the config and hand over the admin role
+    pub arbiter: Pubkey, // Settles disputes opened by winners
+    pub fee_bps: u16,    // Platform fee in basis points
+    pub bond_bps: u16,   // Seller bond in basis points of selling_price
+    pub dispute_window_seconds: i64,
+    pub shipping_window_seconds: i64, // Time the seller has to ship after the draw
+    pub randomness_timeout_slots: u64,
+    #[max_len(8)]
+    pub payment_mints: Vec<PaymentMint>, // Mints raffles may be priced in
+    pub paused: u16, // Bitmask of PAUSE_* flags
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub treasury: Pubkey, // Token account of `mint` that receives platform fees
+}
+
+/// Admin-tunable part of `ProgramConfig`, shared by `initialise_config` and
+/// `update_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigParams {
+    pub arbiter: Pubkey,
+    pub fee_bps: u16,
+    pub bond_bps: u16,
+    pub dispute_window_seconds: i64,
+    pub shipping_window_seconds: i64,
+    pub randomness_timeout_slots: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq,Copy,Debug,InitSpace)]
 pub enum DeliveryStatus {
     Pending,           // Winner selected, awaiting delivery
     Shipped,           // Seller marked as shipped
     Delivered,         // Winner confirmed delivery
     Disputed,          // Winner disputes delivery
     Resolved,          // Dispute resolved
+    Expired,           // Seller missed the shipping deadline
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq,Copy,Debug,InitSpace)]
@@ -59,5 +196,51 @@ pub enum RaffleStatus {
     Completed,
     Cancelled,
     Refunded,
-    Ended
+    Refunding,
+}
+
+/// Everything that can move a raffle between `RaffleStatus`es. The table of
+/// legal moves is `RaffleStatus::next`; instructions change the status only
+/// through `RaffleAccount::transition`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RaffleTransition {
+    CloseSales,    // Active -> Drawing: sold out, or deadline with min_tickets reached
+    DrawWinner,    // Drawing -> Completed
+    Cancel,        // Active -> Cancelled: nobody bought a ticket
+    MissMinimum,   // Active/Drawing -> Refunding: deadline passed below min_tickets
+    SellerDefault, // Completed -> Refunding: dispute lost or shipping deadline missed
+    FinishRefunds, // Refunding -> Refunded: every ticket paid back
+    AbandonDraw,   // Drawing -> Refunding: commit-reveal window closed without a reveal
+}
+
+/// Everything that can move a drawn raffle between `DeliveryStatus`es. The
+/// table of legal moves is `DeliveryStatus::next`; instructions change the
+/// status only through `RaffleAccount::transition_delivery`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeliveryTransition {
+    Ship,            // Pending -> Shipped
+    ConfirmDelivery, // Shipped -> Delivered
+    OpenDispute,     // Shipped/Delivered -> Disputed
+    ResolveDispute,  // Disputed -> Resolved
+    ExpireShipping,  // Pending -> Expired
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy, Debug, InitSpace)]
+pub enum BondStatus {
+    Held,     // In escrow alongside the ticket money
+    Returned, // Paid back to the seller
+    Slashed,  // Paid to the winner after the seller failed them
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy, Debug, InitSpace)]
+pub enum DisputeResolution {
+    ReleaseToSeller,    // Seller delivered, escrow goes to the seller
+    RefundParticipants, // Seller failed, every buyer can claim a refund
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy, Debug, InitSpace)]
+pub enum RandomnessSource {
+    Switchboard,   // Switchboard on-demand oracle
+    CommitReveal,  // Secrets committed and revealed by the participants
+    Deterministic, // Predictable, only accepted with the `test-randomness` feature
 }
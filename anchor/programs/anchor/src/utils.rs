@@ -1,6 +1,16 @@
 // utility function to update raffle fields
 
-use crate::{error::RaffleError, types::RaffleAccount};
+use anchor_lang::prelude::Pubkey;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    constants::{BPS_DENOMINATOR, MAX_PAYMENT_MINTS},
+    error::RaffleError,
+    types::{
+        ConfigParams, DeliveryStatus, DeliveryTransition, DisputeResolution, ParticipantPage,
+        PaymentMint, ProgramConfig, RaffleAccount, RaffleStatus, RaffleTransition, TicketRange,
+    },
+};
 impl RaffleAccount {
     pub fn calculate_progress(entries: u64, max_tickets: u32) -> Result<u32, RaffleError> {
         if max_tickets == 0 {
@@ -21,4 +31,619 @@ impl RaffleAccount {
         
         Ok(progress as u32)
     }
-}
\ No newline at end of file
+
+    /// The only way instructions change `status`.
+    pub fn transition(&mut self, transition: RaffleTransition) -> Result<(), RaffleError> {
+        self.status = self
+            .status
+            .next(transition)
+            .ok_or(RaffleError::InvalidRaffleState)?;
+        Ok(())
+    }
+
+    /// The only way instructions change `product_delivered_status`. Delivery
+    /// only moves once a winner has been drawn.
+    pub fn transition_delivery(
+        &mut self,
+        transition: DeliveryTransition,
+    ) -> Result<(), RaffleError> {
+        if self.status != RaffleStatus::Completed {
+            return Err(RaffleError::RaffleNotCompleted);
+        }
+        self.product_delivered_status = self
+            .product_delivered_status
+            .next(transition)
+            .ok_or(RaffleError::InvalidStatus)?;
+        Ok(())
+    }
+
+    /// Checks the escrow may go to the seller: the winner confirmed delivery,
+    /// the dispute window closed without a dispute, or the arbiter sided with
+    /// the seller. Does not look at `payout_released`.
+    pub fn check_payout_ready(&self, now: i64) -> Result<(), RaffleError> {
+        if self.status != RaffleStatus::Completed {
+            return Err(RaffleError::RaffleNotCompleted);
+        }
+        match self.product_delivered_status {
+            DeliveryStatus::Delivered => Ok(()),
+            DeliveryStatus::Shipped
+                if self
+                    .despute_deadline
+                    .is_some_and(|despute_deadline| now > despute_deadline) =>
+            {
+                Ok(())
+            }
+            DeliveryStatus::Resolved
+                if self.dispute_resolution == Some(DisputeResolution::ReleaseToSeller) =>
+            {
+                Ok(())
+            }
+            DeliveryStatus::Disputed => Err(RaffleError::DisputeOpen),
+            _ => Err(RaffleError::DeliveryNotConfirmed),
+        }
+    }
+
+    /// Turns 32 bytes of randomness into a ticket number uniformly
+    /// distributed over `0..total_entries`.
+    ///
+    /// The bytes are read as four little-endian `u64` words and the first word
+    /// below `u64::MAX - rejection_zone(total_entries)` is reduced modulo
+    /// `total_entries`; that range holds an exact multiple of `total_entries`
+    /// values, so the reduction has no modulo bias. If all four words are
+    /// rejected the bytes are re-hashed with SHA-256 and sampling continues, so
+    /// the result is still a pure function of the revealed randomness.
+    pub fn pick_winning_ticket(
+        randomness: &[u8; 32],
+        total_entries: u64,
+    ) -> Result<u64, RaffleError> {
+        if total_entries == 0 {
+            return Err(RaffleError::NoParticipants);
+        }
+
+        let zone = rejection_zone(total_entries);
+        let mut pool = *randomness;
+        loop {
+            for word in pool.chunks_exact(8) {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(word);
+                let value = u64::from_le_bytes(bytes);
+                if value <= u64::MAX - zone {
+                    return Ok(value % total_entries);
+                }
+            }
+            pool = Sha256::digest(pool).into();
+        }
+    }
+}
+
+impl ProgramConfig {
+    pub fn apply(&mut self, params: ConfigParams) -> Result<(), RaffleError> {
+        if params.fee_bps as u64 > BPS_DENOMINATOR || params.bond_bps as u64 > BPS_DENOMINATOR {
+            return Err(RaffleError::InvalidFee);
+        }
+        if params.dispute_window_seconds <= 0
+            || params.shipping_window_seconds <= 0
+            || params.randomness_timeout_slots == 0
+        {
+            return Err(RaffleError::InvalidConfig);
+        }
+        self.arbiter = params.arbiter;
+        self.fee_bps = params.fee_bps;
+        self.bond_bps = params.bond_bps;
+        self.dispute_window_seconds = params.dispute_window_seconds;
+        self.shipping_window_seconds = params.shipping_window_seconds;
+        self.randomness_timeout_slots = params.randomness_timeout_slots;
+        Ok(())
+    }
+
+    pub fn require_unpaused(&self, flag: u16) -> Result<(), RaffleError> {
+        if self.paused & flag != 0 {
+            return Err(RaffleError::ProgramPaused);
+        }
+        Ok(())
+    }
+
+    pub fn payment_mint(&self, mint: &Pubkey) -> Option<&PaymentMint> {
+        self.payment_mints.iter().find(|entry| entry.mint == *mint)
+    }
+
+    pub fn allow_payment_mint(
+        &mut self,
+        mint: Pubkey,
+        treasury: Pubkey,
+    ) -> Result<(), RaffleError> {
+        if self.payment_mint(&mint).is_some() {
+            return Err(RaffleError::PaymentMintAlreadyAllowed);
+        }
+        if self.payment_mints.len() >= MAX_PAYMENT_MINTS {
+            return Err(RaffleError::TooManyPaymentMints);
+        }
+        self.payment_mints.push(PaymentMint { mint, treasury });
+        Ok(())
+    }
+
+    pub fn remove_payment_mint(&mut self, mint: &Pubkey) -> Result<(), RaffleError> {
+        let index = self
+            .payment_mints
+            .iter()
+            .position(|entry| entry.mint == *mint)
+            .ok_or(RaffleError::PaymentMintNotAllowed)?;
+        self.payment_mints.remove(index);
+        Ok(())
+    }
+}
+
+impl RaffleStatus {
+    /// Status after `transition`, or `None` if it is not allowed from here.
+    pub fn next(self, transition: RaffleTransition) -> Option<RaffleStatus> {
+        use RaffleStatus::*;
+        use RaffleTransition::*;
+
+        match (self, transition) {
+            (Active, CloseSales) => Some(Drawing),
+            (Drawing, DrawWinner) => Some(Completed),
+            (Active, Cancel) => Some(Cancelled),
+            (Active | Drawing, MissMinimum) => Some(Refunding),
+            (Completed, SellerDefault) => Some(Refunding),
+            (Refunding, FinishRefunds) => Some(Refunded),
+            (Drawing, AbandonDraw) => Some(Refunding),
+            _ => None,
+        }
+    }
+}
+
+impl DeliveryStatus {
+    /// Status after `transition`, or `None` if it is not allowed from here.
+    pub fn next(self, transition: DeliveryTransition) -> Option<DeliveryStatus> {
+        use DeliveryStatus::*;
+        use DeliveryTransition::*;
+
+        match (self, transition) {
+            (Pending, Ship) => Some(Shipped),
+            (Shipped, ConfirmDelivery) => Some(Delivered),
+            (Shipped | Delivered, OpenDispute) => Some(Disputed),
+            (Disputed, ResolveDispute) => Some(Resolved),
+            (Pending, ExpireShipping) => Some(Expired),
+            _ => None,
+        }
+    }
+}
+
+/// `bps` basis points of `amount`, rounded down. Used for the platform fee
+/// and the seller bond.
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64, RaffleError> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(RaffleError::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(share).map_err(|_| RaffleError::Overflow)
+}
+
+/// Number of `u64` values at the top of the range that must be rejected so
+/// the remaining `2^64 - zone` values split evenly into `upper_bound` buckets.
+pub fn rejection_zone(upper_bound: u64) -> u64 {
+    (u64::MAX % upper_bound + 1) % upper_bound
+}
+
+impl ParticipantPage {
+    pub const CAPACITY: usize = 48;
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= Self::CAPACITY
+    }
+
+    /// Appends a purchase of `tickets` tickets, numbered from `next_ticket`.
+    pub fn record_purchase(&mut self, buyer: Pubkey, tickets: u32) -> Result<(), RaffleError> {
+        if self.is_full() {
+            return Err(RaffleError::ParticipantPageFull);
+        }
+        let first_ticket = self.next_ticket;
+        self.next_ticket = first_ticket
+            .checked_add(tickets as u64)
+            .ok_or(RaffleError::Overflow)?;
+        self.entries.push(TicketRange {
+            buyer,
+            first_ticket,
+            tickets,
+        });
+        Ok(())
+    }
+
+    /// Maps a ticket number to the buyer holding it, or `None` when the
+    /// ticket was not sold on this page.
+    pub fn ticket_owner(&self, ticket: u64) -> Option<Pubkey> {
+        if ticket < self.first_ticket || ticket >= self.next_ticket {
+            return None;
+        }
+        // Entries are sorted by first_ticket, so the owner is the last entry
+        // starting at or before the ticket
+        let index = self
+            .entries
+            .partition_point(|entry| entry.first_ticket <= ticket);
+        self.entries
+            .get(index.checked_sub(1)?)
+            .map(|entry| entry.buyer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn randomness_from_words(words: [u64; 4]) -> [u8; 32] {
+        let mut randomness = [0u8; 32];
+        for (chunk, word) in randomness.chunks_exact_mut(8).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        randomness
+    }
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(1_000_000, 250).unwrap(), 25_000);
+        assert_eq!(bps_of(399, 250).unwrap(), 9);
+        assert_eq!(bps_of(12_345, 0).unwrap(), 0);
+        assert_eq!(bps_of(12_345, 10_000).unwrap(), 12_345);
+        assert_eq!(bps_of(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn rejects_empty_raffles() {
+        assert!(RaffleAccount::pick_winning_ticket(&[7u8; 32], 0).is_err());
+    }
+
+    #[test]
+    fn single_entry_always_wins() {
+        for seed in 0..=255u8 {
+            assert_eq!(
+                RaffleAccount::pick_winning_ticket(&[seed; 32], 1).unwrap(),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn rejection_zone_leaves_an_exact_multiple() {
+        for upper_bound in (1..2_000u64).chain([u64::MAX / 3, u64::MAX - 1, u64::MAX]) {
+            let zone = rejection_zone(upper_bound);
+            assert!(zone < upper_bound);
+            let accepted = (1u128 << 64) - zone as u128;
+            assert_eq!(accepted % upper_bound as u128, 0, "bound {upper_bound}");
+        }
+    }
+
+    #[test]
+    fn powers_of_two_never_reject() {
+        for shift in 0..64 {
+            assert_eq!(rejection_zone(1u64 << shift), 0);
+        }
+        let randomness = randomness_from_words([u64::MAX, 0, 0, 0]);
+        assert_eq!(
+            RaffleAccount::pick_winning_ticket(&randomness, 1 << 10).unwrap(),
+            (1 << 10) - 1
+        );
+    }
+
+    #[test]
+    fn uses_the_first_accepted_word() {
+        // 2^64 mod 3 == 1, so only u64::MAX falls in the rejection zone
+        assert_eq!(rejection_zone(3), 1);
+        let randomness = randomness_from_words([u64::MAX, 7, 8, 9]);
+        assert_eq!(
+            RaffleAccount::pick_winning_ticket(&randomness, 3).unwrap(),
+            1
+        );
+        let randomness = randomness_from_words([u64::MAX - 1, 7, 8, 9]);
+        assert_eq!(
+            RaffleAccount::pick_winning_ticket(&randomness, 3).unwrap(),
+            (u64::MAX - 1) % 3
+        );
+    }
+
+    #[test]
+    fn rehashes_when_every_word_is_rejected() {
+        let total_entries = (1u64 << 63) + 1;
+        let rejected = u64::MAX;
+        assert!(rejected > u64::MAX - rejection_zone(total_entries));
+
+        let randomness = randomness_from_words([rejected; 4]);
+        let first = RaffleAccount::pick_winning_ticket(&randomness, total_entries).unwrap();
+        let again = RaffleAccount::pick_winning_ticket(&randomness, total_entries).unwrap();
+        assert_eq!(first, again);
+        assert!(first < total_entries);
+    }
+
+    #[test]
+    fn spreads_evenly_across_tickets() {
+        const TOTAL_ENTRIES: u64 = 7;
+        const DRAWS: u32 = 70_000;
+
+        let mut counts = [0u32; TOTAL_ENTRIES as usize];
+        for draw in 0..DRAWS {
+            let randomness: [u8; 32] = Sha256::digest(draw.to_le_bytes()).into();
+            let ticket = RaffleAccount::pick_winning_ticket(&randomness, TOTAL_ENTRIES).unwrap();
+            counts[ticket as usize] += 1;
+        }
+
+        let expected = DRAWS / TOTAL_ENTRIES as u32;
+        for count in counts {
+            assert!(count.abs_diff(expected) < expected / 20, "{counts:?}");
+        }
+    }
+
+    const RAFFLE_STATUSES: [RaffleStatus; 6] = [
+        RaffleStatus::Active,
+        RaffleStatus::Drawing,
+        RaffleStatus::Completed,
+        RaffleStatus::Cancelled,
+        RaffleStatus::Refunded,
+        RaffleStatus::Refunding,
+    ];
+
+    const RAFFLE_TRANSITIONS: [RaffleTransition; 7] = [
+        RaffleTransition::CloseSales,
+        RaffleTransition::DrawWinner,
+        RaffleTransition::Cancel,
+        RaffleTransition::MissMinimum,
+        RaffleTransition::SellerDefault,
+        RaffleTransition::FinishRefunds,
+        RaffleTransition::AbandonDraw,
+    ];
+
+    const DELIVERY_STATUSES: [DeliveryStatus; 6] = [
+        DeliveryStatus::Pending,
+        DeliveryStatus::Shipped,
+        DeliveryStatus::Delivered,
+        DeliveryStatus::Disputed,
+        DeliveryStatus::Resolved,
+        DeliveryStatus::Expired,
+    ];
+
+    const DELIVERY_TRANSITIONS: [DeliveryTransition; 5] = [
+        DeliveryTransition::Ship,
+        DeliveryTransition::ConfirmDelivery,
+        DeliveryTransition::OpenDispute,
+        DeliveryTransition::ResolveDispute,
+        DeliveryTransition::ExpireShipping,
+    ];
+
+    // Fails to compile when a variant is added, so the lists above and the
+    // tables below get updated with it
+    #[allow(dead_code)]
+    fn lists_are_exhaustive(
+        status: RaffleStatus,
+        transition: RaffleTransition,
+        delivery: DeliveryStatus,
+        delivery_transition: DeliveryTransition,
+    ) {
+        match status {
+            RaffleStatus::Active
+            | RaffleStatus::Drawing
+            | RaffleStatus::Completed
+            | RaffleStatus::Cancelled
+            | RaffleStatus::Refunded
+            | RaffleStatus::Refunding => {}
+        }
+        match transition {
+            RaffleTransition::CloseSales
+            | RaffleTransition::DrawWinner
+            | RaffleTransition::Cancel
+            | RaffleTransition::MissMinimum
+            | RaffleTransition::SellerDefault
+            | RaffleTransition::FinishRefunds
+            | RaffleTransition::AbandonDraw => {}
+        }
+        match delivery {
+            DeliveryStatus::Pending
+            | DeliveryStatus::Shipped
+            | DeliveryStatus::Delivered
+            | DeliveryStatus::Disputed
+            | DeliveryStatus::Resolved
+            | DeliveryStatus::Expired => {}
+        }
+        match delivery_transition {
+            DeliveryTransition::Ship
+            | DeliveryTransition::ConfirmDelivery
+            | DeliveryTransition::OpenDispute
+            | DeliveryTransition::ResolveDispute
+            | DeliveryTransition::ExpireShipping => {}
+        }
+    }
+
+    #[test]
+    fn raffle_transition_table() {
+        use RaffleStatus::*;
+        use RaffleTransition::*;
+
+        let legal = [
+            (Active, CloseSales, Drawing),
+            (Drawing, DrawWinner, Completed),
+            (Active, Cancel, Cancelled),
+            (Active, MissMinimum, Refunding),
+            (Drawing, MissMinimum, Refunding),
+            (Completed, SellerDefault, Refunding),
+            (Refunding, FinishRefunds, Refunded),
+            (Drawing, AbandonDraw, Refunding),
+        ];
+
+        for status in RAFFLE_STATUSES {
+            for transition in RAFFLE_TRANSITIONS {
+                let expected = legal
+                    .iter()
+                    .find(|(from, via, _)| *from == status && *via == transition)
+                    .map(|(_, _, to)| *to);
+                assert_eq!(
+                    status.next(transition),
+                    expected,
+                    "{status:?} --{transition:?}-->"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn delivery_transition_table() {
+        use DeliveryStatus::*;
+        use DeliveryTransition::*;
+
+        let legal = [
+            (Pending, Ship, Shipped),
+            (Shipped, ConfirmDelivery, Delivered),
+            (Shipped, OpenDispute, Disputed),
+            (Delivered, OpenDispute, Disputed),
+            (Disputed, ResolveDispute, Resolved),
+            (Pending, ExpireShipping, Expired),
+        ];
+
+        for status in DELIVERY_STATUSES {
+            for transition in DELIVERY_TRANSITIONS {
+                let expected = legal
+                    .iter()
+                    .find(|(from, via, _)| *from == status && *via == transition)
+                    .map(|(_, _, to)| *to);
+                assert_eq!(
+                    status.next(transition),
+                    expected,
+                    "{status:?} --{transition:?}-->"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn terminal_statuses_have_no_way_out() {
+        for status in [RaffleStatus::Cancelled, RaffleStatus::Refunded] {
+            for transition in RAFFLE_TRANSITIONS {
+                assert_eq!(
+                    status.next(transition),
+                    None,
+                    "{status:?} --{transition:?}-->"
+                );
+            }
+        }
+        for status in [DeliveryStatus::Resolved, DeliveryStatus::Expired] {
+            for transition in DELIVERY_TRANSITIONS {
+                assert_eq!(
+                    status.next(transition),
+                    None,
+                    "{status:?} --{transition:?}-->"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_returns_to_active_or_pending() {
+        for status in RAFFLE_STATUSES {
+            for transition in RAFFLE_TRANSITIONS {
+                assert_ne!(status.next(transition), Some(RaffleStatus::Active));
+            }
+        }
+        for status in DELIVERY_STATUSES {
+            for transition in DELIVERY_TRANSITIONS {
+                assert_ne!(status.next(transition), Some(DeliveryStatus::Pending));
+            }
+        }
+    }
+
+    fn page(first_ticket: u64) -> ParticipantPage {
+        ParticipantPage {
+            raffle: Pubkey::new_unique(),
+            page_index: 0,
+            first_ticket,
+            next_ticket: first_ticket,
+            entries: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn record_purchase_numbers_tickets_contiguously() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut page = page(100);
+        page.record_purchase(alice, 3).unwrap();
+        page.record_purchase(bob, 1).unwrap();
+        page.record_purchase(alice, 2).unwrap();
+
+        assert_eq!(page.next_ticket, 106);
+        let starts: Vec<u64> = page.entries.iter().map(|entry| entry.first_ticket).collect();
+        assert_eq!(starts, [100, 103, 104]);
+    }
+
+    #[test]
+    fn record_purchase_rejects_a_full_page() {
+        let mut page = page(0);
+        for _ in 0..ParticipantPage::CAPACITY {
+            page.record_purchase(Pubkey::new_unique(), 1).unwrap();
+        }
+        assert!(page.is_full());
+        assert!(matches!(
+            page.record_purchase(Pubkey::new_unique(), 1),
+            Err(RaffleError::ParticipantPageFull)
+        ));
+        assert_eq!(page.entries.len(), ParticipantPage::CAPACITY);
+        assert_eq!(page.next_ticket, ParticipantPage::CAPACITY as u64);
+    }
+
+    #[test]
+    fn record_purchase_rejects_ticket_overflow() {
+        let mut page = page(u64::MAX - 1);
+        assert!(matches!(
+            page.record_purchase(Pubkey::new_unique(), 2),
+            Err(RaffleError::Overflow)
+        ));
+        assert!(page.entries.is_empty());
+        assert_eq!(page.next_ticket, u64::MAX - 1);
+    }
+
+    #[test]
+    fn ticket_owner_maps_every_ticket_of_a_range() {
+        let (alice, bob, carol) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut page = page(10);
+        page.record_purchase(alice, 3).unwrap();
+        page.record_purchase(bob, 1).unwrap();
+        page.record_purchase(carol, 4).unwrap();
+
+        let owners: Vec<Option<Pubkey>> =
+            (10..18).map(|ticket| page.ticket_owner(ticket)).collect();
+        assert_eq!(
+            owners,
+            [alice, alice, alice, bob, carol, carol, carol, carol].map(Some)
+        );
+    }
+
+    #[test]
+    fn ticket_owner_is_none_outside_the_page() {
+        let mut page = page(10);
+        assert_eq!(page.ticket_owner(10), None);
+
+        page.record_purchase(Pubkey::new_unique(), 5).unwrap();
+        assert_eq!(page.ticket_owner(9), None);
+        assert_eq!(page.ticket_owner(15), None);
+        assert_eq!(page.ticket_owner(u64::MAX), None);
+    }
+
+    #[test]
+    fn next_page_continues_where_the_full_page_ends() {
+        let mut first = page(0);
+        for _ in 0..ParticipantPage::CAPACITY {
+            first.record_purchase(Pubkey::new_unique(), 2).unwrap();
+        }
+        let mut second = page(first.next_ticket);
+        let buyer = Pubkey::new_unique();
+        second.record_purchase(buyer, 1).unwrap();
+
+        let last = first.next_ticket - 1;
+        assert_eq!(
+            first.ticket_owner(last),
+            first.entries.last().map(|entry| entry.buyer)
+        );
+        assert_eq!(second.ticket_owner(last), None);
+        assert_eq!(first.ticket_owner(last + 1), None);
+        assert_eq!(second.ticket_owner(last + 1), Some(buyer));
+    }
+}
@@ -10,7 +10,8 @@ import {
   mintTo,
 } from '@solana/spl-token'
 import { assert } from 'chai'
-import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js'
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from '@solana/web3.js'
+import { createHash, randomBytes } from 'crypto'
 
 // Helper: fetch block time with retries (Surfpool may return null on early slots)
 async function getBlockTimeWithRetry(connection: web3.Connection, retries = 10, delayMs = 1000): Promise<number> {
@@ -25,6 +26,27 @@ async function getBlockTimeWithRetry(connection: web3.Connection, retries = 10,
   return Math.floor(Date.now() / 1000)
 }
 
+function sha256(...parts: Buffer[]): Buffer {
+  const hash = createHash('sha256')
+  parts.forEach((part) => hash.update(part))
+  return hash.digest()
+}
+
+const U64_MAX = new BN(2).pow(new BN(64)).subn(1)
+
+// Mirrors `RaffleAccount::pick_winning_ticket`
+function pickWinningTicket(randomness: Buffer, totalEntries: BN): BN {
+  const zone = U64_MAX.mod(totalEntries).addn(1).mod(totalEntries)
+  let pool = randomness
+  for (;;) {
+    for (let offset = 0; offset < 32; offset += 8) {
+      const value = new BN(pool.subarray(offset, offset + 8), 'le')
+      if (value.lte(U64_MAX.sub(zone))) return value.mod(totalEntries)
+    }
+    pool = sha256(pool)
+  }
+}
+
 describe('raffle', () => {
   const provider = anchor.AnchorProvider.env()
   anchor.setProvider(provider)
@@ -40,9 +62,57 @@ describe('raffle', () => {
   let buyer: Keypair
   let buyerTokenAccount: PublicKey
   let raffleDeadline: BN
+  let configPda: PublicKey
+  // Commit-reveal secrets of each buyer, revealed after the deadline
+  const secrets = new Map<string, Buffer>()
 
   const connection = provider.connection
 
+  const participantPageAddress = (pageIndex: number) =>
+    web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('participant_page'), rafflePda.toBuffer(), new BN(pageIndex).toArrayLike(Buffer, 'le', 4)],
+      program.programId,
+    )[0]
+
+  const ticketReceiptAddress = (owner: PublicKey) =>
+    web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('ticket_receipt'), rafflePda.toBuffer(), owner.toBuffer()],
+      program.programId,
+    )[0]
+
+  // Buys on the current participant page and commits a fresh secret
+  async function buyAndCommit(owner: Keypair, tokenAccount: PublicKey, numTickets: number) {
+    const raffle = await program.account.raffleAccount.fetch(rafflePda)
+    const tx = await program.methods
+      .buyTickets(numTickets)
+      .accounts({
+        buyer: owner.publicKey,
+        buyerTokenAccont: tokenAccount,
+        raffleAccount: rafflePda,
+        config: configPda,
+        escrowPaymentAccount: escrowPaymentAccountPda,
+        ticketReceipt: ticketReceiptAddress(owner.publicKey),
+        participantPage: participantPageAddress(raffle.currentPage),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc()
+
+    const secret = randomBytes(32)
+    secrets.set(owner.publicKey.toString(), secret)
+    await program.methods
+      .commitEntropy([...sha256(rafflePda.toBuffer(), owner.publicKey.toBuffer(), secret)])
+      .accounts({
+        buyer: owner.publicKey,
+        raffleAccount: rafflePda,
+        ticketReceipt: ticketReceiptAddress(owner.publicKey),
+      })
+      .signers([owner])
+      .rpc()
+    return tx
+  }
+
   before(async () => {
     buyer = Keypair.generate()
     seller = payer.publicKey
@@ -92,6 +162,44 @@ describe('raffle', () => {
       }
     }
 
+    // Initialise the global config and allow the test mint; only the upgrade
+    // authority (the provider wallet under `anchor test`) may do this
+    ;[configPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('config')], program.programId)
+    const [programData] = web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111'),
+    )
+    try {
+      await program.methods
+        .initialiseConfig({
+          arbiter: payer.publicKey,
+          feeBps: 0,
+          bondBps: 0,
+          disputeWindowSeconds: new BN(30 * 24 * 60 * 60),
+          shippingWindowSeconds: new BN(14 * 24 * 60 * 60),
+          randomnessTimeoutSlots: new BN(1500),
+        })
+        .accounts({
+          config: configPda,
+          admin: payer.publicKey,
+          programData,
+        })
+        .rpc()
+    } catch (e: any) {
+      if (!e.toString().includes('already in use')) {
+        throw e
+      }
+    }
+    await program.methods
+      .addPaymentMint()
+      .accounts({
+        config: configPda,
+        admin: payer.publicKey,
+        paymentMint: paymentMint,
+        treasury: sellerTokenAccount,
+      })
+      .rpc()
+
     // Derive PDAs here using live counter value so they're available to ALL tests
     const accountInfo = await program.account.counter.fetch(counterPda)
     const counterBuffer = Buffer.alloc(8)
@@ -131,13 +239,26 @@ describe('raffle', () => {
 
     // Use retry helper — Surfpool may return null block time on early slots
     const currentTs = await getBlockTimeWithRetry(connection)
-    raffleDeadline = new BN(currentTs + 10)
+    // Leaves time to buy and commit secrets before the deadline
+    raffleDeadline = new BN(currentTs + 20)
 
     const tx = await program.methods
-      .createRaffle(itemName, itemDescription, itemImageUri, sellingPrice, ticketPrice, 10, 40, raffleDeadline)
+      .createRaffle(
+        itemName,
+        itemDescription,
+        itemImageUri,
+        sellingPrice,
+        ticketPrice,
+        5,
+        40,
+        raffleDeadline,
+        // The local validator has no Switchboard oracle
+        { commitReveal: {} },
+      )
       .accounts({
         seller: payer.publicKey,
         counter: counterPda,
+        config: configPda,
         paymentMint: paymentMint,
         sellerTokenAccount: sellerTokenAccount,
         raffleAccount: rafflePda,
@@ -180,25 +301,14 @@ describe('raffle', () => {
 
     const totalCollected = 10 * 1000000 * 5
 
-    const tx = await program.methods
-      .buyTickets(numTickets)
-      .accounts({
-        buyer: buyer.publicKey,
-        buyerTokenAccont: buyerTokenAccount,
-        raffleAccount: rafflePda,
-        escrowPaymentAccount: escrowPaymentAccountPda,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([buyer])
-      .rpc()
+    const tx = await buyAndCommit(buyer, buyerTokenAccount, numTickets)
 
     const raffleAccount = await program.account.raffleAccount.fetch(rafflePda)
-    console.log('total participants:', raffleAccount.participants.length)
+    console.log('total participants:', raffleAccount.participantCount)
     console.log('total entries:', raffleAccount.totalEntries.toNumber())
 
     assert.equal(raffleAccount.totalEntries.toNumber(), 5, 'total entries should be 5')
-    assert.equal(raffleAccount.participants.length, 1, 'total participants should be 1')
+    assert.equal(raffleAccount.participantCount, 1, 'total participants should be 1')
     assert.equal(raffleAccount.totalCollected.toNumber(), totalCollected, 'total collected should be 50000000')
     assert.equal(raffleAccount.progress, 12, 'Progress should be 12%')
     console.log('progress:', raffleAccount.progress)
@@ -220,18 +330,7 @@ describe('raffle', () => {
     const airdropSig2 = await connection.requestAirdrop(secondBuyer.publicKey, 2 * web3.LAMPORTS_PER_SOL)
     await connection.confirmTransaction(airdropSig2)
 
-    await program.methods
-      .buyTickets(1)
-      .accounts({
-        buyer: secondBuyer.publicKey,
-        buyerTokenAccont: secondBuyerTokenAccount,
-        raffleAccount: rafflePda,
-        escrowPaymentAccount: escrowPaymentAccountPda,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .signers([secondBuyer])
-      .rpc()
+    await buyAndCommit(secondBuyer, secondBuyerTokenAccount, 1)
 
     // Poll until on-chain clock passes the deadline
     console.log('Waiting for deadline to pass...')
@@ -251,25 +350,75 @@ describe('raffle', () => {
       if (waited > 180) throw new Error('Timed out waiting for raffle deadline to pass')
     }
 
-    const raffleBefore = await program.account.raffleAccount.fetch(rafflePda)
-    const participants = raffleBefore.participants
+    const participants = [buyer.publicKey, secondBuyer.publicKey]
     console.log(
       'Participants before draw:',
       participants.map((p) => p.toString()),
     )
 
+    for (const owner of [buyer, secondBuyer]) {
+      await program.methods
+        .revealEntropy([...secrets.get(owner.publicKey.toString())!])
+        .accounts({
+          buyer: owner.publicKey,
+          raffleAccount: rafflePda,
+          ticketReceipt: ticketReceiptAddress(owner.publicKey),
+        })
+        .signers([owner])
+        .rpc()
+    }
+
+    await program.methods
+      .closeSales()
+      .accounts({
+        raffleAccount: rafflePda,
+        caller: payer.publicKey,
+      })
+      .rpc()
+    // Every secret is revealed, so the draw can be committed right away
+    await program.methods
+      .requestDraw()
+      .accounts({
+        raffleAccount: rafflePda,
+        config: configPda,
+        randomnessAccountData: null,
+        authority: payer.publicKey,
+      })
+      .rpc()
+
+    // The caller names the participant page holding the winning ticket
+    const raffleBefore = await program.account.raffleAccount.fetch(rafflePda)
+    const randomness = sha256(Buffer.from(raffleBefore.entropy), rafflePda.toBuffer())
+    const winningTicket = pickWinningTicket(randomness, raffleBefore.totalEntries)
+    let pageIndex = 0
+    for (; pageIndex <= raffleBefore.currentPage; pageIndex++) {
+      const page = await program.account.participantPage.fetch(participantPageAddress(pageIndex))
+      if (page.nextTicket.gt(winningTicket)) break
+    }
+    const [drawRecord] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('draw_record'), rafflePda.toBuffer()],
+      program.programId,
+    )
+
     const tx = await program.methods
       .drawWinner()
       .accounts({
         raffleAccount: rafflePda,
-        signer: payer.publicKey,
-        clock: SYSVAR_CLOCK_PUBKEY,
+        config: configPda,
+        randomnessAccountData: null,
+        participantPage: participantPageAddress(pageIndex),
+        drawRecord,
+        payer: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc()
 
     console.log('Draw winner transaction:', tx)
 
     const raffleAfter = await program.account.raffleAccount.fetch(rafflePda)
+    const record = await program.account.drawRecord.fetch(drawRecord)
+    assert.equal(record.winningTicket.toString(), winningTicket.toString(), 'Drawn ticket should match')
+    assert.equal(record.participantPage, pageIndex, 'Draw should use the computed page')
 
     // @ts-ignore
     assert.property(raffleAfter.status, 'completed', 'Status should be Completed')
@@ -1,9 +1,11 @@
-import { Connection, Keypair, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js'
+import { Connection, Keypair, PublicKey, Transaction, TransactionInstruction } from '@solana/web3.js'
 import { createServer } from 'http'
 import { Server } from 'socket.io'
 import * as dotenv from 'dotenv'
-import { AnchorProvider, Program, Wallet } from '@coral-xyz/anchor'
+import { AnchorProvider, BN, Program, Wallet } from '@coral-xyz/anchor'
+import * as sb from '@switchboard-xyz/on-demand'
 import idl from './idl/Raffle.json'
+import { createHash } from 'crypto'
 import * as fs from 'fs'
 
 dotenv.config()
@@ -45,12 +47,189 @@ function toNumber(value: { toNumber?: () => number } | number): number {
   return typeof value === 'number' ? value : value.toNumber?.() ?? Number(value)
 }
 
-function hasStatus(status: unknown, expected: string): boolean {
-  if (typeof status === 'string') {
-    return status.toLowerCase() === expected.toLowerCase()
+function hasVariant(value: unknown, expected: string): boolean {
+  if (typeof value === 'string') {
+    return value.toLowerCase() === expected.toLowerCase()
   }
 
-  return Boolean(status && typeof status === 'object' && expected in status)
+  return Boolean(value && typeof value === 'object' && expected in value)
+}
+
+function sha256(...parts: Buffer[]): Buffer {
+  const hash = createHash('sha256')
+  parts.forEach((part) => hash.update(part))
+  return hash.digest()
+}
+
+const U64_MAX = (1n << 64n) - 1n
+
+/**
+ * Mirrors `RaffleAccount::pick_winning_ticket`: the first of the four
+ * little-endian u64 words outside the rejection zone, reduced modulo
+ * `totalEntries`, re-hashing the bytes if all four are rejected.
+ */
+function pickWinningTicket(randomness: Buffer, totalEntries: bigint): bigint {
+  const zone = ((U64_MAX % totalEntries) + 1n) % totalEntries
+  let pool = randomness
+  for (;;) {
+    for (let offset = 0; offset < 32; offset += 8) {
+      const value = pool.readBigUInt64LE(offset)
+      if (value <= U64_MAX - zone) return value % totalEntries
+    }
+    pool = sha256(pool)
+  }
+}
+
+/**
+ * The 32 bytes `draw_winner` reveals for commit-reveal and deterministic
+ * raffles, recomputed from the raffle's state.
+ */
+function expectedRandomness(raffleKey: PublicKey, account: any): Buffer {
+  if (hasVariant(account.randomnessSource, 'commitReveal')) {
+    return sha256(Buffer.from(account.entropy), raffleKey.toBuffer())
+  }
+  const commitSlot = Buffer.alloc(8)
+  commitSlot.writeBigUInt64LE(BigInt(account.randomnessCommitSlot.toString()))
+  return sha256(raffleKey.toBuffer(), commitSlot)
+}
+
+// Offset of `value` in a Switchboard `RandomnessAccountData`, after the
+// discriminator, authority, queue, seed slothash, seed slot, oracle and
+// reveal slot.
+const RANDOMNESS_VALUE_OFFSET = 8 + 32 + 32 + 32 + 8 + 32 + 8
+
+/** Simulates the oracle reveal on its own to read the value it writes. */
+async function revealedValue(revealIx: TransactionInstruction, randomnessAccount: PublicKey): Promise<Buffer> {
+  const tx = new Transaction().add(revealIx)
+  tx.feePayer = wallet.publicKey
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash
+  const { value } = await connection.simulateTransaction(tx, undefined, [randomnessAccount])
+  if (value.err) {
+    throw new Error(`reveal failed in simulation: ${JSON.stringify(value.err)}`)
+  }
+  const account = value.accounts?.[0]
+  if (!account) {
+    throw new Error('simulation did not return the randomness account')
+  }
+  const data = Buffer.from(account.data[0], 'base64')
+  return data.subarray(RANDOMNESS_VALUE_OFFSET, RANDOMNESS_VALUE_OFFSET + 32)
+}
+
+function participantPageAddress(raffleKey: PublicKey, pageIndex: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('participant_page'), raffleKey.toBuffer(), new BN(pageIndex).toArrayLike(Buffer, 'le', 4)],
+    program.programId,
+  )[0]
+}
+
+/** Finds the participant page that sold `ticket`. */
+async function pageHolding(raffleKey: PublicKey, account: any, ticket: bigint): Promise<PublicKey> {
+  for (let pageIndex = 0; pageIndex <= account.currentPage; pageIndex++) {
+    const address = participantPageAddress(raffleKey, pageIndex)
+    const page = await (program.account as any).participantPage.fetch(address)
+    if (BigInt(page.firstTicket.toString()) <= ticket && ticket < BigInt(page.nextTicket.toString())) {
+      return address
+    }
+  }
+  throw new Error(`no participant page holds ticket ${ticket}`)
+}
+
+let switchboard: { program: Program; queue: PublicKey } | undefined
+
+async function loadSwitchboard() {
+  if (!switchboard) {
+    const programId = await sb.getProgramId(connection)
+    const sbIdl = await Program.fetchIdl(programId, provider)
+    if (!sbIdl) throw new Error('Switchboard IDL not found')
+    const queue = await sb.getDefaultQueue(connection.rpcEndpoint)
+    switchboard = { program: new Program(sbIdl, provider), queue: queue.pubkey }
+  }
+  return switchboard
+}
+
+async function sendV0(ixs: TransactionInstruction[], signers: Keypair[] = []): Promise<string> {
+  const tx = await sb.asV0Tx({
+    connection,
+    ixs,
+    payer: wallet.publicKey,
+    signers: [keypair, ...signers],
+    computeUnitPrice: 75_000,
+    computeUnitLimitMultiple: 1.3,
+  })
+  const signature = await connection.sendTransaction(tx)
+  await connection.confirmTransaction(signature, 'confirmed')
+  return signature
+}
+
+/**
+ * Commits the draw. Switchboard raffles get a fresh randomness account,
+ * created in its own transaction and committed alongside `request_draw`;
+ * commit-reveal raffles fail here until every secret is revealed or the
+ * reveal window closes, and are retried on the next scan.
+ */
+async function requestDraw(raffleKey: PublicKey, account: any): Promise<string> {
+  if (!hasVariant(account.randomnessSource, 'switchboard')) {
+    return program.methods
+      .requestDraw()
+      .accounts({
+        raffleAccount: raffleKey,
+        randomnessAccountData: null,
+        authority: wallet.publicKey,
+      } as any)
+      .rpc()
+  }
+
+  const { program: sbProgram, queue } = await loadSwitchboard()
+  const randomnessKeypair = Keypair.generate()
+  const [randomness, createIx] = await sb.Randomness.create(sbProgram as any, randomnessKeypair, queue)
+  await sendV0([createIx], [randomnessKeypair])
+
+  const commitIx = await randomness.commitIx(queue)
+  const requestIx = await program.methods
+    .requestDraw()
+    .accounts({
+      raffleAccount: raffleKey,
+      randomnessAccountData: randomness.pubkey,
+      authority: wallet.publicKey,
+    } as any)
+    .instruction()
+  return sendV0([commitIx, requestIx])
+}
+
+/**
+ * Draws the winner, passing the participant page that holds the winning
+ * ticket. Switchboard draws share a transaction with the oracle reveal.
+ */
+async function drawWinner(raffleKey: PublicKey, account: any): Promise<string> {
+  const isSwitchboard = hasVariant(account.randomnessSource, 'switchboard')
+  let revealIx: TransactionInstruction | undefined
+  let randomness: Buffer
+  if (isSwitchboard) {
+    const { program: sbProgram } = await loadSwitchboard()
+    const randomnessAccount = new sb.Randomness(sbProgram as any, account.randomnessAccount)
+    revealIx = await randomnessAccount.revealIx()
+    randomness = await revealedValue(revealIx, account.randomnessAccount)
+  } else {
+    randomness = expectedRandomness(raffleKey, account)
+  }
+
+  const ticket = pickWinningTicket(randomness, BigInt(account.totalEntries.toString()))
+  const participantPage = await pageHolding(raffleKey, account, ticket)
+  const [drawRecord] = PublicKey.findProgramAddressSync(
+    [Buffer.from('draw_record'), raffleKey.toBuffer()],
+    program.programId,
+  )
+  const drawIx = await program.methods
+    .drawWinner()
+    .accounts({
+      raffleAccount: raffleKey,
+      randomnessAccountData: isSwitchboard ? account.randomnessAccount : null,
+      participantPage,
+      drawRecord,
+      payer: wallet.publicKey,
+    } as any)
+    .instruction()
+  return sendV0(revealIx ? [revealIx, drawIx] : [drawIx])
 }
 
 let isProcessing = false
@@ -59,11 +238,13 @@ let isProcessing = false
  * Periodic keeper job:
  * - Runs every 30 seconds
  * - Scans all raffle accounts
- * - For each raffle where:
- *   - on-chain time > deadline
- *   - status == Drawing
- *   - (other invariants are enforced by the on-chain program)
- *   it sends a `draw_winner` instruction.
+ * - For each raffle where on-chain time > deadline:
+ *   - status == Active: sends `close_sales`, which moves it to Drawing or
+ *     to a refundable state depending on `min_tickets`
+ *   - status == Drawing without a commitment: sends `request_draw`
+ *   - status == Drawing with a commitment: sends `draw_winner` with the
+ *     participant page holding the winning ticket
+ *   (other invariants are enforced by the on-chain program)
  */
 async function processRaffles() {
   if (isProcessing) {
@@ -88,9 +269,25 @@ async function processRaffles() {
 
     for (const { publicKey, account } of raffles) {
       const deadline = toNumber(account.deadline)
-      const isDrawing = hasStatus(account.status, 'drawing')
+      const isDrawing = hasVariant(account.status, 'drawing')
 
-      if (account.participants.length <= 0) {
+      if (hasVariant(account.status, 'active') && nowTs > deadline) {
+        try {
+          const tx = await program.methods
+            .closeSales()
+            .accounts({
+              raffleAccount: publicKey,
+              caller: wallet.publicKey,
+            })
+            .rpc()
+          console.log(`Keeper: close_sales succeeded for raffle ${publicKey.toBase58()}, tx=${tx}`)
+        } catch (e) {
+          console.error(`Keeper: close_sales failed for raffle ${publicKey.toBase58()}`, e)
+        }
+        continue
+      }
+
+      if (toNumber(account.participantCount) <= 0) {
         console.log(`Keeper: skipping ${publicKey.toBase58()} because it has no participants`)
         continue
       }
@@ -99,23 +296,22 @@ async function processRaffles() {
       if (!isDrawing) continue
       if (nowTs <= deadline) continue
 
+      if (!account.randomnessAccount) {
+        try {
+          const tx = await requestDraw(publicKey, account)
+          console.log(`Keeper: request_draw succeeded for raffle ${publicKey.toBase58()}, tx=${tx}`)
+        } catch (e) {
+          console.error(`Keeper: request_draw failed for raffle ${publicKey.toBase58()}`, e)
+        }
+        continue
+      }
+
       console.log(
         `Keeper: triggering draw_winner for raffle ${publicKey.toBase58()} (deadline=${deadline}, now=${nowTs})`,
       )
 
       try {
-        const tx = await program.methods
-          .drawWinner()
-          .accounts({
-            raffleAccount: publicKey,
-            signer: wallet.publicKey,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          })
-          .rpc()
-
-        if (tx) {
-          console.log('Winner selected!')
-        }
+        const tx = await drawWinner(publicKey, account)
         console.log(`Keeper: draw_winner succeeded for raffle ${publicKey.toBase58()}, tx=${tx}`)
       } catch (e) {
         console.error(`Keeper: draw_winner failed for raffle ${publicKey.toBase58()}`, e)
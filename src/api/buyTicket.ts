@@ -48,13 +48,32 @@ export const buyTicket = () => {
           throw new Error('Raffle key not found!')
         }
 
+        const [config] = PublicKey.findProgramAddressSync([Buffer.from('config')], program.programId)
+        const [ticketReceipt] = PublicKey.findProgramAddressSync(
+          [Buffer.from('ticket_receipt'), raffleKey.toBuffer(), publicKey.toBuffer()],
+          program.programId,
+        )
+        // Purchases are appended to the raffle's current participant page
+        const raffle = await program.account.raffleAccount.fetch(raffleKey)
+        const [participantPage] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from('participant_page'),
+            raffleKey.toBuffer(),
+            new BN(raffle.currentPage).toArrayLike(Buffer, 'le', 4),
+          ],
+          program.programId,
+        )
+
         const tx = await (program.methods as any)
           .buyTickets(numTickets)
           .accounts({
             buyer: publicKey,
             buyerTokenAccont: tokenATA,
             raffleAccount: raffleKey,
+            config,
             escrowPaymentAccount,
+            ticketReceipt,
+            participantPage,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -28,6 +28,7 @@ export const CreateRaffle = () => {
       sellingPrice,
       deadline,
       ticketPrice,
+      randomnessSource = 'switchboard',
     }: CreateRaffleInputs) => {
       try {
         if (!publicKey) {
@@ -46,6 +47,7 @@ export const CreateRaffle = () => {
           throw new Error('Program not found!')
         }
         const [counterPda] = PublicKey.findProgramAddressSync([Buffer.from('global-counter')], program.programId)
+        const [config] = PublicKey.findProgramAddressSync([Buffer.from('config')], program.programId)
         const counterValue = await getCounterValue()
 
         const sellerTokenAccount = await createTokenAccount()
@@ -83,10 +85,12 @@ export const CreateRaffle = () => {
             minTickets,
             maxTickets,
             new BN(deadline),
+            { [randomnessSource]: {} },
           )
           .accounts({
             seller: publicKey,
             counter: counterPda,
+            config,
             paymentMint,
             sellerTokenAccount: sellerTokenAccount,
             raffleAccount: rafflePda,
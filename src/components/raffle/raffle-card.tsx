@@ -30,7 +30,7 @@ interface RaffleCardProps {
   totalCollected: number
   maxTickets: number
   deadline: number
-  status: 'active' | 'drawing' | 'completed' | 'cancelled' | 'refunded' | 'ended'
+  status: 'active' | 'drawing' | 'completed' | 'cancelled' | 'refunding' | 'refunded'
   sellerKey: string
   claimed: boolean
   winner: string | undefined
@@ -144,7 +144,7 @@ export const RaffleCard: React.FC<RaffleCardProps> = ({
   const isActive = status === 'active'
   const isDrawing = status === 'drawing'
   const isCompleted = status === 'completed'
-  const isInactive = status === 'cancelled' || status === 'refunded' || status === 'ended'
+  const isInactive = status === 'cancelled' || status === 'refunding' || status === 'refunded'
   const hasWinner = isCompleted && !!winner
 
   const formattedTicketPrice = ticketPrice
@@ -5,7 +5,7 @@ export const getRaffleStatus = (status: any): RaffleStatus => {
   if (status.drawing) return 'drawing'
   if (status.completed) return 'completed'
   if (status.cancelled) return 'cancelled'
+  if (status.refunding) return 'refunding'
   if (status.refunded) return 'refunded'
-  if (status.ended) return 'ended'
   throw new Error('Invalid raffle status')
 }
@@ -14,30 +14,23 @@ export type Raffle = {
   }
   instructions: [
     {
-      name: 'buyTickets'
-      discriminator: [48, 16, 122, 137, 24, 214, 198, 58]
+      name: 'abandonDraw'
+      docs: [
+        'Anyone can send a commit-reveal raffle to refunds once its reveal',
+        'window has closed without a single reveal, since `request_draw` can',
+        'then never succeed. Buyers claim with `claim_refund` and the seller',
+        'gets the bond back with `reclaim_bond`.',
+      ]
+      discriminator: [113, 241, 26, 200, 163, 204, 202, 172]
       accounts: [
-        {
-          name: 'buyer'
-          writable: true
-          signer: true
-        },
-        {
-          name: 'buyerTokenAccont'
-          writable: true
-        },
         {
           name: 'raffleAccount'
           writable: true
-        },
-        {
-          name: 'escrowPaymentAccount'
-          writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+                value: [114, 97, 102, 102, 108, 101]
               },
               {
                 kind: 'account'
@@ -53,197 +46,237 @@ export type Raffle = {
           }
         },
         {
-          name: 'tokenProgram'
-          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+          name: 'caller'
+          signer: true
         },
+      ]
+      args: []
+    },
+    {
+      name: 'addPaymentMint'
+      docs: ['Allows raffles to be priced in `payment_mint`, with platform fees for', 'that mint going to `treasury`.']
+      discriminator: [122, 149, 9, 186, 21, 201, 60, 241]
+      accounts: [
         {
-          name: 'systemProgram'
-          address: '11111111111111111111111111111111'
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
         },
-      ]
-      args: [
         {
-          name: 'numTickets'
-          type: 'u8'
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+        {
+          name: 'paymentMint'
+        },
+        {
+          name: 'treasury'
         },
       ]
+      args: []
     },
     {
-      name: 'createRaffle'
-      discriminator: [226, 206, 159, 34, 213, 207, 98, 126]
+      name: 'buyTickets'
+      discriminator: [48, 16, 122, 137, 24, 214, 198, 58]
       accounts: [
         {
-          name: 'seller'
+          name: 'buyer'
           writable: true
           signer: true
         },
         {
-          name: 'counter'
+          name: 'buyerTokenAccont'
+          writable: true
+        },
+        {
+          name: 'raffleAccount'
           writable: true
+        },
+        {
+          name: 'config'
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+                value: [99, 111, 110, 102, 105, 103]
               },
             ]
           }
         },
         {
-          name: 'paymentMint'
-          docs: ['Payment token mint (USDC, SOL wrapped, etc.)']
-        },
-        {
-          name: 'sellerTokenAccount'
-          docs: ["Seller's token account"]
+          name: 'escrowPaymentAccount'
           writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
         },
         {
-          name: 'raffleAccount'
-          docs: ['Raffle PDA']
+          name: 'ticketReceipt'
           writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [114, 97, 102, 102, 108, 101]
+                value: [116, 105, 99, 107, 101, 116, 95, 114, 101, 99, 101, 105, 112, 116]
               },
               {
                 kind: 'account'
-                path: 'seller'
+                path: 'raffleAccount'
               },
               {
                 kind: 'account'
-                path: 'counter.counter'
-                account: 'counter'
+                path: 'buyer'
               },
             ]
           }
         },
         {
-          name: 'escrowPaymentAccount'
-          docs: ['Escrow token account - THE FIX IS HERE', 'Must be initialized AFTER raffle_account exists']
+          name: 'participantPage'
           writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+                value: [112, 97, 114, 116, 105, 99, 105, 112, 97, 110, 116, 95, 112, 97, 103, 101]
               },
               {
                 kind: 'account'
-                path: 'seller'
+                path: 'raffleAccount'
               },
               {
                 kind: 'account'
-                path: 'counter.counter'
-                account: 'counter'
+                path: 'raffle_account.current_page'
+                account: 'raffleAccount'
               },
             ]
           }
         },
         {
           name: 'tokenProgram'
-          docs: ['Programs']
           address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
         },
-        {
-          name: 'associatedTokenProgram'
-          address: 'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL'
-        },
         {
           name: 'systemProgram'
           address: '11111111111111111111111111111111'
         },
-        {
-          name: 'rent'
-          address: 'SysvarRent111111111111111111111111111111111'
-        },
       ]
       args: [
         {
-          name: 'itemName'
-          type: 'string'
-        },
-        {
-          name: 'itemDescription'
-          type: 'string'
-        },
-        {
-          name: 'itemImageUri'
-          type: 'string'
-        },
-        {
-          name: 'sellingPrice'
-          type: 'u64'
-        },
-        {
-          name: 'ticketPrice'
-          type: 'u64'
-        },
-        {
-          name: 'minTickets'
-          type: 'u32'
-        },
-        {
-          name: 'maxTickets'
-          type: 'u32'
-        },
-        {
-          name: 'deadline'
-          type: 'i64'
+          name: 'numTickets'
+          type: 'u8'
         },
       ]
     },
     {
-      name: 'drawWinner'
-      discriminator: [250, 103, 118, 147, 219, 235, 169, 220]
+      name: 'cancelRaffle'
+      docs: [
+        'Lets the seller withdraw a listing nobody has bought into yet. The bond',
+        'is returned, then the escrow token account and the raffle account are',
+        'closed and their rent goes back to the seller.',
+      ]
+      discriminator: [135, 191, 223, 141, 192, 186, 234, 254]
       accounts: [
         {
-          name: 'raffleAccount'
+          name: 'seller'
           writable: true
+          signer: true
+          relations: ['raffleAccount']
         },
         {
-          name: 'randomnessAccountData'
+          name: 'sellerTokenAccount'
+          writable: true
         },
-      ]
-      args: []
-    },
-    {
-      name: 'initialiseCounter'
-      discriminator: [85, 25, 135, 234, 240, 145, 129, 186]
-      accounts: [
         {
-          name: 'counter'
+          name: 'raffleAccount'
           writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
               },
             ]
           }
         },
         {
-          name: 'signer'
+          name: 'escrowPaymentAccount'
           writable: true
-          signer: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
         },
         {
-          name: 'systemProgram'
-          address: '11111111111111111111111111111111'
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
         },
       ]
       args: []
     },
     {
-      name: 'markDelivered'
-      discriminator: [240, 118, 188, 142, 64, 85, 107, 18]
+      name: 'claimRefund'
+      docs: [
+        "Returns a buyer's tickets at face value once the deadline has passed",
+        'without the raffle reaching `min_tickets`.',
+      ]
+      discriminator: [15, 16, 30, 161, 255, 228, 97, 60]
       accounts: [
         {
           name: 'buyer'
           writable: true
+          signer: true
+          relations: ['ticketReceipt']
+        },
+        {
+          name: 'buyerTokenAccount'
+          writable: true
         },
         {
           name: 'raffleAccount'
@@ -252,7 +285,7 @@ export type Raffle = {
             seeds: [
               {
                 kind: 'const'
-                value: [114, 97, 102, 102, 108, 101, 95, 97, 99, 99, 111, 117, 110, 116]
+                value: [114, 97, 102, 102, 108, 101]
               },
               {
                 kind: 'account'
@@ -261,41 +294,82 @@ export type Raffle = {
               },
               {
                 kind: 'account'
-                path: 'counter.counter'
-                account: 'counter'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
               },
             ]
           }
         },
         {
-          name: 'counter'
+          name: 'escrowPaymentAccount'
           writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
               },
             ]
           }
         },
-      ]
-      args: [
         {
-          name: 'trackingInfo'
-          type: {
-            option: 'string'
+          name: 'ticketReceipt'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [116, 105, 99, 107, 101, 116, 95, 114, 101, 99, 101, 105, 112, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'buyer'
+              },
+            ]
           }
         },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
       ]
+      args: []
     },
     {
-      name: 'markShipped'
-      discriminator: [239, 5, 66, 105, 238, 17, 89, 97]
+      name: 'closeRaffle'
+      docs: [
+        'Gives the seller back the rent of a raffle that is over: paid out or',
+        'fully refunded. Anything still in escrow (a bond nobody reclaimed,',
+        'stray deposits) goes to the seller before the escrow is closed, unless',
+        '`finalize_raffle` already closed it. With a `raffle_archive` account a',
+        'compact summary is kept on-chain. A cancelled raffle needs no closing:',
+        '`cancel_raffle` closes it right away.',
+      ]
+      discriminator: [220, 129, 128, 51, 70, 66, 209, 124]
       accounts: [
         {
           name: 'seller'
           writable: true
+          signer: true
+          relations: ['raffleAccount']
+        },
+        {
+          name: 'sellerTokenAccount'
+          writable: true
         },
         {
           name: 'raffleAccount'
@@ -304,7 +378,7 @@ export type Raffle = {
             seeds: [
               {
                 kind: 'const'
-                value: [114, 97, 102, 102, 108, 101, 95, 97, 99, 99, 111, 117, 110, 116]
+                value: [114, 97, 102, 102, 108, 101]
               },
               {
                 kind: 'account'
@@ -313,261 +387,2780 @@ export type Raffle = {
               },
               {
                 kind: 'account'
-                path: 'counter.counter'
-                account: 'counter'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
               },
             ]
           }
         },
         {
-          name: 'counter'
+          name: 'escrowPaymentAccount'
+          docs: ['Empty once `finalize_raffle` closed it']
           writable: true
           pda: {
             seeds: [
               {
                 kind: 'const'
-                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
               },
             ]
           }
         },
-      ]
-      args: [
         {
-          name: 'trackingInfo'
-          type: {
-            option: 'string'
+          name: 'raffleArchive'
+          writable: true
+          optional: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101, 95, 97, 114, 99, 104, 105, 118, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
           }
         },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+        {
+          name: 'systemProgram'
+          address: '11111111111111111111111111111111'
+        },
       ]
+      args: []
     },
     {
-      name: 'requestDraw'
-      discriminator: [22, 180, 8, 81, 47, 21, 86, 159]
+      name: 'closeSales'
+      docs: [
+        'Anyone can end ticket sales once the deadline has passed. The raffle',
+        'moves to `Drawing` if it reached `min_tickets`, otherwise to',
+        '`Refunding` (straight on to `Refunded` if nothing was collected).',
+      ]
+      discriminator: [63, 216, 175, 193, 204, 39, 113, 225]
       accounts: [
         {
           name: 'raffleAccount'
           writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
         },
         {
-          name: 'randomnessAccountData'
-        },
-        {
-          name: 'authority'
+          name: 'caller'
           signer: true
         },
       ]
       args: []
     },
-  ]
-  accounts: [
-    {
-      name: 'counter'
-      discriminator: [255, 176, 4, 245, 188, 253, 124, 25]
-    },
-    {
-      name: 'raffleAccount'
-      discriminator: [148, 199, 5, 56, 54, 26, 78, 102]
-    },
-  ]
-  events: [
-    {
-      name: 'productDelivered'
-      discriminator: [186, 173, 91, 163, 189, 234, 114, 131]
-    },
-    {
-      name: 'productShipped'
-      discriminator: [91, 178, 86, 185, 155, 204, 16, 129]
-    },
-    {
-      name: 'raffleCreated'
-      discriminator: [178, 172, 201, 96, 233, 171, 6, 99]
-    },
-    {
-      name: 'ticketsBought'
-      discriminator: [204, 103, 221, 60, 70, 142, 88, 233]
-    },
-  ]
-  errors: [
-    {
-      code: 6000
-      name: 'invalidPrice'
-      msg: 'Invalid price'
-    },
-    {
-      code: 6001
-      name: 'invalidTicketCount'
-      msg: 'Invalid ticket count'
-    },
-    {
-      code: 6002
-      name: 'invalidDeadline'
-      msg: 'Invalid deadline'
-    },
-    {
-      code: 6003
-      name: 'raffleNotActive'
-      msg: 'Raffle not active'
-    },
-    {
-      code: 6004
-      name: 'deadlinePassed'
-      msg: 'Deadline passed'
-    },
-    {
-      code: 6005
-      name: 'maxTicketsReached'
-      msg: 'Max tickets reached'
-    },
-    {
-      code: 6006
-      name: 'overflow'
-      msg: 'Arithmetic overflow'
-    },
-    {
-      code: 6007
-      name: 'cannotDrawYet'
-      msg: 'Cannot draw yet'
-    },
-    {
-      code: 6008
-      name: 'minTicketsNotReached'
-      msg: 'Min tickets not reached'
-    },
-    {
-      code: 6009
-      name: 'invalidStatus'
-      msg: 'Invalid status'
-    },
-    {
-      code: 6010
-      name: 'noParticipants'
-      msg: 'No participants'
-    },
-    {
-      code: 6011
-      name: 'raffleNotCompleted'
-      msg: 'Raffle not completed'
-    },
-    {
-      code: 6012
-      name: 'notWinner'
-      msg: 'Not winner'
-    },
-    {
-      code: 6013
-      name: 'alreadyClaimed'
-      msg: 'Already claimed'
-    },
-    {
-      code: 6014
-      name: 'notSeller'
-      msg: 'Not seller'
-    },
-    {
-      code: 6015
-      name: 'ticketsAlreadySold'
-      msg: 'Tickets already sold'
-    },
-    {
-      code: 6016
-      name: 'deadlineNotReached'
-      msg: 'Deadline not reached'
-    },
-    {
-      code: 6017
-      name: 'minTicketsReached'
-      msg: 'Min tickets reached'
-    },
-    {
-      code: 6018
-      name: 'unauthorized'
-      msg: 'Unauthorise request!'
-    },
     {
-      code: 6019
-      name: 'invalidRandomnessAccount'
-      msg: 'Invalid randomness account data!'
+      name: 'commitEntropy'
+      docs: [
+        "Commits a participant's secret for a `CommitReveal` raffle. Must happen",
+        'before the deadline so nobody can commit after seeing other reveals.',
+      ]
+      discriminator: [91, 173, 89, 22, 226, 128, 74, 62]
+      accounts: [
+        {
+          name: 'buyer'
+          signer: true
+          relations: ['ticketReceipt']
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'ticketReceipt'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [116, 105, 99, 107, 101, 116, 95, 114, 101, 99, 101, 105, 112, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'buyer'
+              },
+            ]
+          }
+        },
+      ]
+      args: [
+        {
+          name: 'commitment'
+          type: {
+            array: ['u8', 32]
+          }
+        },
+      ]
     },
     {
-      code: 6020
-      name: 'randomnessTooOld'
-      msg: 'Random data too old!'
+      name: 'createRaffle'
+      discriminator: [226, 206, 159, 34, 213, 207, 98, 126]
+      accounts: [
+        {
+          name: 'seller'
+          writable: true
+          signer: true
+        },
+        {
+          name: 'counter'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+              },
+            ]
+          }
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'paymentMint'
+          docs: ['Payment token mint (USDC, SOL wrapped, etc.)']
+        },
+        {
+          name: 'sellerTokenAccount'
+          docs: ["Seller's token account"]
+          writable: true
+        },
+        {
+          name: 'raffleAccount'
+          docs: ['Raffle PDA']
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'seller'
+              },
+              {
+                kind: 'account'
+                path: 'counter.counter'
+                account: 'counter'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          docs: ['Escrow token account - THE FIX IS HERE', 'Must be initialized AFTER raffle_account exists']
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'seller'
+              },
+              {
+                kind: 'account'
+                path: 'counter.counter'
+                account: 'counter'
+              },
+            ]
+          }
+        },
+        {
+          name: 'tokenProgram'
+          docs: ['Programs']
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+        {
+          name: 'associatedTokenProgram'
+          address: 'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL'
+        },
+        {
+          name: 'systemProgram'
+          address: '11111111111111111111111111111111'
+        },
+        {
+          name: 'rent'
+          address: 'SysvarRent111111111111111111111111111111111'
+        },
+      ]
+      args: [
+        {
+          name: 'itemName'
+          type: 'string'
+        },
+        {
+          name: 'itemDescription'
+          type: 'string'
+        },
+        {
+          name: 'itemImageUri'
+          type: 'string'
+        },
+        {
+          name: 'sellingPrice'
+          type: 'u64'
+        },
+        {
+          name: 'ticketPrice'
+          type: 'u64'
+        },
+        {
+          name: 'minTickets'
+          type: 'u32'
+        },
+        {
+          name: 'maxTickets'
+          type: 'u32'
+        },
+        {
+          name: 'deadline'
+          type: 'i64'
+        },
+        {
+          name: 'randomnessSource'
+          type: {
+            defined: {
+              name: 'randomnessSource'
+            }
+          }
+        },
+      ]
+    },
+    {
+      name: 'drawWinner'
+      discriminator: [250, 103, 118, 147, 219, 235, 169, 220]
+      accounts: [
+        {
+          name: 'raffleAccount'
+          writable: true
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'randomnessAccountData'
+          docs: ['Only required for Switchboard raffles.']
+          optional: true
+        },
+        {
+          name: 'participantPage'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [112, 97, 114, 116, 105, 99, 105, 112, 97, 110, 116, 95, 112, 97, 103, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'participant_page.page_index'
+                account: 'participantPage'
+              },
+            ]
+          }
+        },
+        {
+          name: 'drawRecord'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [100, 114, 97, 119, 95, 114, 101, 99, 111, 114, 100]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'payer'
+          writable: true
+          signer: true
+        },
+        {
+          name: 'systemProgram'
+          address: '11111111111111111111111111111111'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'expireUnshipped'
+      docs: [
+        'Anyone can call this once the seller let `shipping_deadline` pass',
+        'without `mark_shipped`. Participants get their tickets back through',
+        '`claim_refund` and the seller bond is slashed to the winner.',
+      ]
+      discriminator: [182, 132, 67, 120, 83, 135, 135, 60]
+      accounts: [
+        {
+          name: 'caller'
+          signer: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'winnerTokenAccount'
+          docs: ['Receives the slashed bond as compensation']
+          writable: true
+        },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'finalizeRaffle'
+      docs: [
+        'Permissionless settlement crank for the keeper. Pays the seller exactly',
+        'like `release_to_seller` if that has not happened yet, then sweeps',
+        'anything left in escrow to the seller and closes it.',
+      ]
+      discriminator: [158, 169, 24, 177, 51, 49, 21, 42]
+      accounts: [
+        {
+          name: 'caller'
+          signer: true
+        },
+        {
+          name: 'seller'
+          writable: true
+        },
+        {
+          name: 'sellerTokenAccount'
+          writable: true
+        },
+        {
+          name: 'treasury'
+          writable: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'initialiseConfig'
+      docs: [
+        'Creates the global `ProgramConfig`; the signer becomes its admin. Only',
+        "the program's upgrade authority may call it, so nobody can front-run",
+        'the deployer and take over fees, treasury, arbiter and pausing.',
+      ]
+      discriminator: [24, 253, 60, 19, 229, 154, 93, 147]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          writable: true
+          signer: true
+        },
+        {
+          name: 'program'
+          address: 'F1o3uGF7BP9tTvNXEPLFHtynamJfwpFkBAwsds1nEx8p'
+        },
+        {
+          name: 'programData'
+        },
+        {
+          name: 'systemProgram'
+          address: '11111111111111111111111111111111'
+        },
+      ]
+      args: [
+        {
+          name: 'params'
+          type: {
+            defined: {
+              name: 'configParams'
+            }
+          }
+        },
+      ]
+    },
+    {
+      name: 'initialiseCounter'
+      discriminator: [85, 25, 135, 234, 240, 145, 129, 186]
+      accounts: [
+        {
+          name: 'counter'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [103, 108, 111, 98, 97, 108, 45, 99, 111, 117, 110, 116, 101, 114]
+              },
+            ]
+          }
+        },
+        {
+          name: 'signer'
+          writable: true
+          signer: true
+        },
+        {
+          name: 'systemProgram'
+          address: '11111111111111111111111111111111'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'markDelivered'
+      docs: ['Winner confirms receipt. Only valid once the seller marked the prize', 'shipped.']
+      discriminator: [240, 118, 188, 142, 64, 85, 107, 18]
+      accounts: [
+        {
+          name: 'winner'
+          signer: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+      ]
+      args: [
+        {
+          name: 'trackingInfo'
+          type: {
+            option: 'string'
+          }
+        },
+      ]
+    },
+    {
+      name: 'markShipped'
+      docs: [
+        'Seller marks the prize shipped before `shipping_deadline`, which opens',
+        'the dispute window. Only valid from `Pending`.',
+      ]
+      discriminator: [239, 5, 66, 105, 238, 17, 89, 97]
+      accounts: [
+        {
+          name: 'seller'
+          signer: true
+          relations: ['raffleAccount']
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+      ]
+      args: [
+        {
+          name: 'trackingInfo'
+          type: {
+            option: 'string'
+          }
+        },
+      ]
+    },
+    {
+      name: 'openDispute'
+      docs: [
+        'Lets the winner contest a shipment before `despute_deadline`. Only a',
+        'hash of the reason/evidence is stored; the material itself lives',
+        'off-chain. Escrow stays locked until the arbiter resolves the dispute.',
+      ]
+      discriminator: [137, 25, 99, 119, 23, 223, 161, 42]
+      accounts: [
+        {
+          name: 'winner'
+          signer: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+      ]
+      args: [
+        {
+          name: 'evidenceHash'
+          type: {
+            array: ['u8', 32]
+          }
+        },
+      ]
+    },
+    {
+      name: 'pause'
+      docs: ['Sets the `PAUSE_*` bits in `flags`. Refunds cannot be paused.']
+      discriminator: [211, 22, 221, 251, 74, 121, 193, 47]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+      ]
+      args: [
+        {
+          name: 'flags'
+          type: 'u16'
+        },
+      ]
+    },
+    {
+      name: 'reclaimBond'
+      docs: [
+        "Returns the seller bond when the raffle failed through nobody's fault,",
+        'i.e. the deadline passed without reaching `min_tickets`.',
+      ]
+      discriminator: [18, 133, 105, 58, 246, 52, 103, 31]
+      accounts: [
+        {
+          name: 'seller'
+          signer: true
+          relations: ['raffleAccount']
+        },
+        {
+          name: 'sellerTokenAccount'
+          writable: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'releaseToSeller'
+      docs: [
+        'Pays the escrowed pot out to the seller once the winner confirmed',
+        'delivery or the dispute window closed without a dispute. Everything',
+        "collected above `selling_price` is the seller's surplus. The platform",
+        'fee is split off to the treasury first; the seller bond comes back',
+        'fee-free on top.',
+      ]
+      discriminator: [218, 83, 41, 9, 49, 54, 255, 56]
+      accounts: [
+        {
+          name: 'seller'
+          signer: true
+          relations: ['raffleAccount']
+        },
+        {
+          name: 'sellerTokenAccount'
+          writable: true
+        },
+        {
+          name: 'treasury'
+          writable: true
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'removePaymentMint'
+      docs: ['Stops new raffles from using `mint`. Existing raffles are unaffected.']
+      discriminator: [112, 22, 67, 45, 123, 26, 166, 193]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+      ]
+      args: [
+        {
+          name: 'mint'
+          type: 'pubkey'
+        },
+      ]
+    },
+    {
+      name: 'requestDraw'
+      discriminator: [22, 180, 8, 81, 47, 21, 86, 159]
+      accounts: [
+        {
+          name: 'raffleAccount'
+          writable: true
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'randomnessAccountData'
+          docs: ['Only required for Switchboard raffles.']
+          optional: true
+        },
+        {
+          name: 'authority'
+          signer: true
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'rerequestDraw'
+      docs: [
+        'Replaces a Switchboard commitment that can no longer be drawn from, so',
+        'a raffle cannot be stuck in `Drawing` by an oracle that never answers',
+        'or by a randomness account that was re-committed under it. After the',
+        'configured `randomness_timeout_slots` the old account must either be',
+        'unrevealed or hold a different commitment; a revealed value of the',
+        'stored commitment is drawn with `draw_winner` instead. Whoever holds',
+        'that account sees the reveal first and could withhold it, so only the',
+        'admin may replace it, otherwise a caller could discard an outcome they',
+        'dislike.',
+      ]
+      discriminator: [35, 16, 66, 76, 247, 50, 169, 190]
+      accounts: [
+        {
+          name: 'raffleAccount'
+          writable: true
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'abandonedRandomnessAccountData'
+        },
+        {
+          name: 'randomnessAccountData'
+        },
+        {
+          name: 'authority'
+          signer: true
+        },
+      ]
+      args: []
+    },
+    {
+      name: 'resolveDispute'
+      docs: [
+        'Settles an open dispute. `ReleaseToSeller` unlocks `release_to_seller`;',
+        '`RefundParticipants` moves the raffle to `Refunding` so every buyer can',
+        'get their tickets back through `claim_refund`, and slashes the seller',
+        'bond to the winner.',
+      ]
+      discriminator: [231, 6, 202, 6, 96, 103, 12, 230]
+      accounts: [
+        {
+          name: 'arbiter'
+          signer: true
+          relations: ['config']
+        },
+        {
+          name: 'config'
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'escrowPaymentAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [101, 115, 99, 114, 111, 119, 95, 112, 97, 121, 109, 101, 110, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'winnerTokenAccount'
+          docs: ['Receives the slashed bond when the dispute goes against the seller']
+          writable: true
+        },
+        {
+          name: 'tokenProgram'
+          address: 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA'
+        },
+      ]
+      args: [
+        {
+          name: 'resolution'
+          type: {
+            defined: {
+              name: 'disputeResolution'
+            }
+          }
+        },
+      ]
+    },
+    {
+      name: 'revealEntropy'
+      docs: [
+        'Reveals a secret committed with `commit_entropy` and folds it into the',
+        "raffle's entropy. Only possible between the deadline and the draw",
+        'being requested.',
+      ]
+      discriminator: [112, 157, 174, 123, 233, 83, 16, 238]
+      accounts: [
+        {
+          name: 'buyer'
+          signer: true
+          relations: ['ticketReceipt']
+        },
+        {
+          name: 'raffleAccount'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [114, 97, 102, 102, 108, 101]
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.seller'
+                account: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'raffle_account.raffle_id'
+                account: 'raffleAccount'
+              },
+            ]
+          }
+        },
+        {
+          name: 'ticketReceipt'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [116, 105, 99, 107, 101, 116, 95, 114, 101, 99, 101, 105, 112, 116]
+              },
+              {
+                kind: 'account'
+                path: 'raffleAccount'
+              },
+              {
+                kind: 'account'
+                path: 'buyer'
+              },
+            ]
+          }
+        },
+      ]
+      args: [
+        {
+          name: 'secret'
+          type: {
+            array: ['u8', 32]
+          }
+        },
+      ]
+    },
+    {
+      name: 'setAdmin'
+      discriminator: [251, 163, 0, 52, 91, 194, 187, 92]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+      ]
+      args: [
+        {
+          name: 'newAdmin'
+          type: 'pubkey'
+        },
+      ]
+    },
+    {
+      name: 'unpause'
+      docs: ['Clears the `PAUSE_*` bits in `flags`.']
+      discriminator: [169, 144, 4, 38, 10, 141, 188, 255]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+      ]
+      args: [
+        {
+          name: 'flags'
+          type: 'u16'
+        },
+      ]
+    },
+    {
+      name: 'updateConfig'
+      discriminator: [29, 158, 252, 191, 10, 83, 219, 99]
+      accounts: [
+        {
+          name: 'config'
+          writable: true
+          pda: {
+            seeds: [
+              {
+                kind: 'const'
+                value: [99, 111, 110, 102, 105, 103]
+              },
+            ]
+          }
+        },
+        {
+          name: 'admin'
+          signer: true
+          relations: ['config']
+        },
+      ]
+      args: [
+        {
+          name: 'params'
+          type: {
+            defined: {
+              name: 'configParams'
+            }
+          }
+        },
+      ]
+    },
+  ]
+  accounts: [
+    {
+      name: 'counter'
+      discriminator: [255, 176, 4, 245, 188, 253, 124, 25]
+    },
+    {
+      name: 'drawRecord'
+      discriminator: [249, 216, 8, 178, 230, 90, 230, 184]
+    },
+    {
+      name: 'participantPage'
+      discriminator: [203, 153, 106, 73, 228, 51, 68, 241]
+    },
+    {
+      name: 'programConfig'
+      discriminator: [196, 210, 90, 231, 144, 149, 140, 63]
+    },
+    {
+      name: 'raffleAccount'
+      discriminator: [148, 199, 5, 56, 54, 26, 78, 102]
+    },
+    {
+      name: 'raffleArchive'
+      discriminator: [199, 162, 191, 5, 31, 34, 44, 58]
+    },
+    {
+      name: 'ticketReceipt'
+      discriminator: [33, 39, 194, 207, 210, 208, 161, 103]
+    },
+  ]
+  events: [
+    {
+      name: 'bondReturned'
+      discriminator: [41, 85, 150, 247, 210, 150, 160, 118]
+    },
+    {
+      name: 'bondSlashed'
+      discriminator: [59, 7, 252, 195, 234, 156, 42, 54]
+    },
+    {
+      name: 'disputeOpened'
+      discriminator: [239, 222, 102, 235, 193, 85, 1, 214]
+    },
+    {
+      name: 'disputeResolved'
+      discriminator: [121, 64, 249, 153, 139, 128, 236, 187]
+    },
+    {
+      name: 'drawAbandoned'
+      discriminator: [198, 195, 120, 81, 222, 171, 252, 243]
+    },
+    {
+      name: 'entropyCommitted'
+      discriminator: [171, 37, 82, 239, 51, 40, 236, 64]
+    },
+    {
+      name: 'entropyRevealed'
+      discriminator: [252, 143, 233, 248, 245, 161, 141, 48]
+    },
+    {
+      name: 'productDelivered'
+      discriminator: [186, 173, 91, 163, 189, 234, 114, 131]
+    },
+    {
+      name: 'productShipped'
+      discriminator: [91, 178, 86, 185, 155, 204, 16, 129]
+    },
+    {
+      name: 'programPaused'
+      discriminator: [247, 9, 236, 38, 105, 35, 112, 115]
+    },
+    {
+      name: 'programUnpaused'
+      discriminator: [193, 183, 146, 52, 180, 17, 21, 26]
+    },
+    {
+      name: 'raffleCancelled'
+      discriminator: [123, 83, 254, 127, 53, 244, 159, 102]
+    },
+    {
+      name: 'raffleClosed'
+      discriminator: [148, 47, 204, 246, 27, 111, 118, 49]
+    },
+    {
+      name: 'raffleCreated'
+      discriminator: [178, 172, 201, 96, 233, 171, 6, 99]
+    },
+    {
+      name: 'raffleSettled'
+      discriminator: [233, 134, 180, 156, 208, 35, 79, 201]
+    },
+    {
+      name: 'randomnessRerequested'
+      discriminator: [181, 185, 34, 82, 17, 108, 190, 181]
+    },
+    {
+      name: 'refundClaimed'
+      discriminator: [136, 64, 242, 99, 4, 244, 208, 130]
+    },
+    {
+      name: 'salesClosed'
+      discriminator: [145, 190, 254, 208, 83, 93, 13, 189]
+    },
+    {
+      name: 'sellerPaid'
+      discriminator: [199, 114, 123, 95, 205, 204, 221, 37]
+    },
+    {
+      name: 'shippingExpired'
+      discriminator: [53, 215, 151, 236, 124, 245, 206, 246]
+    },
+    {
+      name: 'ticketsBought'
+      discriminator: [204, 103, 221, 60, 70, 142, 88, 233]
+    },
+    {
+      name: 'winnerDrawn'
+      discriminator: [213, 103, 5, 118, 145, 75, 146, 120]
+    },
+  ]
+  errors: [
+    {
+      code: 6000
+      name: 'invalidPrice'
+      msg: 'Invalid price'
+    },
+    {
+      code: 6001
+      name: 'invalidTicketCount'
+      msg: 'Invalid ticket count'
+    },
+    {
+      code: 6002
+      name: 'invalidDeadline'
+      msg: 'Invalid deadline'
+    },
+    {
+      code: 6003
+      name: 'raffleNotActive'
+      msg: 'Raffle not active'
+    },
+    {
+      code: 6004
+      name: 'deadlinePassed'
+      msg: 'Deadline passed'
+    },
+    {
+      code: 6005
+      name: 'maxTicketsReached'
+      msg: 'Max tickets reached'
+    },
+    {
+      code: 6006
+      name: 'overflow'
+      msg: 'Arithmetic overflow'
+    },
+    {
+      code: 6007
+      name: 'cannotDrawYet'
+      msg: 'Cannot draw yet'
+    },
+    {
+      code: 6008
+      name: 'minTicketsNotReached'
+      msg: 'Min tickets not reached'
+    },
+    {
+      code: 6009
+      name: 'invalidStatus'
+      msg: 'Invalid status'
+    },
+    {
+      code: 6010
+      name: 'noParticipants'
+      msg: 'No participants'
+    },
+    {
+      code: 6011
+      name: 'raffleNotCompleted'
+      msg: 'Raffle not completed'
+    },
+    {
+      code: 6012
+      name: 'notWinner'
+      msg: 'Not winner'
+    },
+    {
+      code: 6013
+      name: 'alreadyClaimed'
+      msg: 'Already claimed'
+    },
+    {
+      code: 6014
+      name: 'notSeller'
+      msg: 'Not seller'
+    },
+    {
+      code: 6015
+      name: 'ticketsAlreadySold'
+      msg: 'Tickets already sold'
+    },
+    {
+      code: 6016
+      name: 'deadlineNotReached'
+      msg: 'Deadline not reached'
+    },
+    {
+      code: 6017
+      name: 'minTicketsReached'
+      msg: 'Min tickets reached'
+    },
+    {
+      code: 6018
+      name: 'unauthorized'
+      msg: 'Unauthorise request!'
+    },
+    {
+      code: 6019
+      name: 'invalidRandomnessAccount'
+      msg: 'Invalid randomness account data!'
+    },
+    {
+      code: 6020
+      name: 'randomnessTooOld'
+      msg: 'Random data too old!'
+    },
+    {
+      code: 6021
+      name: 'enrtiesFull'
+      msg: 'Entries full! You missed the opportunity!'
+    },
+    {
+      code: 6022
+      name: 'underFlow'
+      msg: 'Arithmetic under flow'
+    },
+    {
+      code: 6023
+      name: 'raffleFull'
+      msg: 'Participants full!'
+    },
+    {
+      code: 6024
+      name: 'randomnessExpired'
+      msg: 'Randomness expired!'
+    },
+    {
+      code: 6025
+      name: 'randomnessAlreadyRevealed'
+      msg: 'Randomness already expired!'
+    },
+    {
+      code: 6026
+      name: 'invalidRaffleState'
+      msg: 'Invalid Raffle state!'
+    },
+    {
+      code: 6027
+      name: 'randomnessNotResolved'
+      msg: 'Randomness not resolved!'
+    },
+    {
+      code: 6028
+      name: 'winnerAlreadySelected'
+      msg: 'Winner already selected!'
+    },
+    {
+      code: 6029
+      name: 'randomnessAlreadyRequested'
+      msg: 'Randomness already requested!'
+    },
+    {
+      code: 6030
+      name: 'randomnessNotRequested'
+      msg: 'Randomness has not been requested yet!'
+    },
+    {
+      code: 6031
+      name: 'notParticipant'
+      msg: 'Signer is not a participant of this raffle!'
+    },
+    {
+      code: 6032
+      name: 'deliveryNotConfirmed'
+      msg: 'Delivery has not been confirmed yet!'
+    },
+    {
+      code: 6033
+      name: 'disputeOpen'
+      msg: 'A dispute is open for this raffle!'
+    },
+    {
+      code: 6034
+      name: 'disputeWindowClosed'
+      msg: 'Dispute window has closed!'
+    },
+    {
+      code: 6035
+      name: 'noOpenDispute'
+      msg: 'No dispute is open for this raffle!'
+    },
+    {
+      code: 6036
+      name: 'participantPageFull'
+      msg: 'Participant page is full!'
+    },
+    {
+      code: 6037
+      name: 'wrongParticipantPage'
+      msg: 'Winning ticket is not on this participant page!'
+    },
+    {
+      code: 6038
+      name: 'randomnessStillPending'
+      msg: 'Randomness commitment is still within its reveal window!'
+    },
+    {
+      code: 6039
+      name: 'randomnessSourceUnavailable'
+      msg: 'Randomness source is not available in this build!'
+    },
+    {
+      code: 6040
+      name: 'wrongRandomnessSource'
+      msg: 'Raffle does not use this randomness source!'
+    },
+    {
+      code: 6041
+      name: 'entropyAlreadyCommitted'
+      msg: 'Entropy already committed!'
+    },
+    {
+      code: 6042
+      name: 'invalidEntropyReveal'
+      msg: 'Revealed secret does not match the commitment!'
+    },
+    {
+      code: 6043
+      name: 'noEntropyRevealed'
+      msg: 'No participant has revealed entropy yet!'
+    },
+    {
+      code: 6044
+      name: 'revealWindowOpen'
+      msg: 'Participants are still revealing entropy!'
+    },
+    {
+      code: 6045
+      name: 'invalidFee'
+      msg: 'Fee and bond must not exceed 10000 basis points!'
+    },
+    {
+      code: 6046
+      name: 'invalidConfig'
+      msg: 'Invalid config parameters!'
+    },
+    {
+      code: 6047
+      name: 'paymentMintNotAllowed'
+      msg: 'Payment mint is not allowed!'
+    },
+    {
+      code: 6048
+      name: 'paymentMintAlreadyAllowed'
+      msg: 'Payment mint is already allowed!'
+    },
+    {
+      code: 6049
+      name: 'tooManyPaymentMints'
+      msg: 'Too many payment mints!'
+    },
+    {
+      code: 6050
+      name: 'invalidTreasury'
+      msg: 'Treasury does not hold the payment mint!'
+    },
+    {
+      code: 6051
+      name: 'programPaused'
+      msg: 'This instruction is paused!'
+    },
+    {
+      code: 6052
+      name: 'invalidPauseFlags'
+      msg: 'Unknown pause flags!'
+    },
+    {
+      code: 6053
+      name: 'bondNotHeld'
+      msg: 'Seller bond is not held in escrow!'
+    },
+    {
+      code: 6054
+      name: 'bondNotReclaimable'
+      msg: 'Seller bond can only be reclaimed after a raffle that ended without a winner!'
+    },
+    {
+      code: 6055
+      name: 'shippingDeadlinePassed'
+      msg: 'Shipping deadline has passed!'
+    },
+    {
+      code: 6056
+      name: 'shippingDeadlineNotReached'
+      msg: 'Shipping deadline not reached yet!'
+    },
+    {
+      code: 6057
+      name: 'raffleNotSettled'
+      msg: 'Raffle is not settled yet!'
+    },
+    {
+      code: 6058
+      name: 'entropyWasRevealed'
+      msg: 'A participant revealed entropy, the draw can go ahead!'
+    },
+  ]
+  types: [
+    {
+      name: 'bondReturned'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'amount'
+            type: 'u64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'bondSlashed'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'winner'
+            type: 'pubkey'
+          },
+          {
+            name: 'amount'
+            type: 'u64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'bondStatus'
+      type: {
+        kind: 'enum'
+        variants: [
+          {
+            name: 'held'
+          },
+          {
+            name: 'returned'
+          },
+          {
+            name: 'slashed'
+          },
+        ]
+      }
+    },
+    {
+      name: 'configParams'
+      docs: ['Admin-tunable part of `ProgramConfig`, shared by `initialise_config` and', '`update_config`.']
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'arbiter'
+            type: 'pubkey'
+          },
+          {
+            name: 'feeBps'
+            type: 'u16'
+          },
+          {
+            name: 'bondBps'
+            type: 'u16'
+          },
+          {
+            name: 'disputeWindowSeconds'
+            type: 'i64'
+          },
+          {
+            name: 'shippingWindowSeconds'
+            type: 'i64'
+          },
+          {
+            name: 'randomnessTimeoutSlots'
+            type: 'u64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'counter'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'counter'
+            type: 'u64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'deliveryStatus'
+      type: {
+        kind: 'enum'
+        variants: [
+          {
+            name: 'pending'
+          },
+          {
+            name: 'shipped'
+          },
+          {
+            name: 'delivered'
+          },
+          {
+            name: 'disputed'
+          },
+          {
+            name: 'resolved'
+          },
+          {
+            name: 'expired'
+          },
+        ]
+      }
+    },
+    {
+      name: 'disputeOpened'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'winner'
+            type: 'pubkey'
+          },
+          {
+            name: 'evidenceHash'
+            type: {
+              array: ['u8', 32]
+            }
+          },
+          {
+            name: 'openedAt'
+            type: 'i64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'disputeResolution'
+      type: {
+        kind: 'enum'
+        variants: [
+          {
+            name: 'releaseToSeller'
+          },
+          {
+            name: 'refundParticipants'
+          },
+        ]
+      }
+    },
+    {
+      name: 'disputeResolved'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'arbiter'
+            type: 'pubkey'
+          },
+          {
+            name: 'resolution'
+            type: {
+              defined: {
+                name: 'disputeResolution'
+              }
+            }
+          },
+          {
+            name: 'resolvedAt'
+            type: 'i64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'drawAbandoned'
+      docs: ['A commit-reveal raffle whose reveal window closed with no reveals; every', 'buyer can claim a refund.']
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'entropyCommitments'
+            type: 'u32'
+          },
+          {
+            name: 'abandonedAt'
+            type: 'i64'
+          },
+        ]
+      }
+    },
+    {
+      name: 'drawRecord'
+      docs: [
+        'Everything needed to replay a draw: feed `randomness` and `total_entries`',
+        'to the algorithm named by `algorithm_version`, then look the ticket up in',
+        "the raffle's participant pages.",
+      ]
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'randomnessSource'
+            type: {
+              defined: {
+                name: 'randomnessSource'
+              }
+            }
+          },
+          {
+            name: 'randomnessAccount'
+            type: 'pubkey'
+          },
+          {
+            name: 'randomness'
+            type: {
+              array: ['u8', 32]
+            }
+          },
+          {
+            name: 'seedSlot'
+            type: 'u64'
+          },
+          {
+            name: 'revealSlot'
+            type: 'u64'
+          },
+          {
+            name: 'totalEntries'
+            type: 'u64'
+          },
+          {
+            name: 'winningTicket'
+            type: 'u64'
+          },
+          {
+            name: 'winner'
+            type: 'pubkey'
+          },
+          {
+            name: 'participantPage'
+            type: 'u32'
+          },
+          {
+            name: 'algorithmVersion'
+            type: 'u8'
+          },
+          {
+            name: 'drawnAt'
+            type: 'i64'
+          },
+          {
+            name: 'bump'
+            type: 'u8'
+          },
+        ]
+      }
+    },
+    {
+      name: 'entropyCommitted'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'buyer'
+            type: 'pubkey'
+          },
+          {
+            name: 'commitment'
+            type: {
+              array: ['u8', 32]
+            }
+          },
+        ]
+      }
+    },
+    {
+      name: 'entropyRevealed'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'buyer'
+            type: 'pubkey'
+          },
+          {
+            name: 'reveals'
+            type: 'u32'
+          },
+          {
+            name: 'commitments'
+            type: 'u32'
+          },
+        ]
+      }
+    },
+    {
+      name: 'participantPage'
+      docs: [
+        'Append-only log of purchases for a raffle, split across PDAs seeded by',
+        '`page_index` so the number of buyers is bounded only by `max_tickets`.',
+        'Page `n + 1` starts at the ticket where page `n` ends.',
+      ]
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'pageIndex'
+            type: 'u32'
+          },
+          {
+            name: 'firstTicket'
+            type: 'u64'
+          },
+          {
+            name: 'nextTicket'
+            type: 'u64'
+          },
+          {
+            name: 'entries'
+            type: {
+              vec: {
+                defined: {
+                  name: 'ticketRange'
+                }
+              }
+            }
+          },
+          {
+            name: 'bump'
+            type: 'u8'
+          },
+        ]
+      }
+    },
+    {
+      name: 'paymentMint'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'mint'
+            type: 'pubkey'
+          },
+          {
+            name: 'treasury'
+            type: 'pubkey'
+          },
+        ]
+      }
+    },
+    {
+      name: 'productDelivered'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'winner'
+            type: 'pubkey'
+          },
+          {
+            name: 'deliveredAt'
+            type: {
+              option: 'i64'
+            }
+          },
+        ]
+      }
     },
     {
-      code: 6021
-      name: 'enrtiesFull'
-      msg: 'Entries full! You missed the opportunity!'
+      name: 'productShipped'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'winner'
+            type: 'pubkey'
+          },
+          {
+            name: 'shippedAt'
+            type: {
+              option: 'i64'
+            }
+          },
+        ]
+      }
+    },
+    {
+      name: 'programConfig'
+      docs: ['Global settings, initialised once. Every instruction that moves money', 'reads its parameters from here.']
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'admin'
+            type: 'pubkey'
+          },
+          {
+            name: 'arbiter'
+            type: 'pubkey'
+          },
+          {
+            name: 'feeBps'
+            type: 'u16'
+          },
+          {
+            name: 'bondBps'
+            type: 'u16'
+          },
+          {
+            name: 'disputeWindowSeconds'
+            type: 'i64'
+          },
+          {
+            name: 'shippingWindowSeconds'
+            type: 'i64'
+          },
+          {
+            name: 'randomnessTimeoutSlots'
+            type: 'u64'
+          },
+          {
+            name: 'paymentMints'
+            type: {
+              vec: {
+                defined: {
+                  name: 'paymentMint'
+                }
+              }
+            }
+          },
+          {
+            name: 'paused'
+            type: 'u16'
+          },
+          {
+            name: 'bump'
+            type: 'u8'
+          },
+        ]
+      }
+    },
+    {
+      name: 'programPaused'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'admin'
+            type: 'pubkey'
+          },
+          {
+            name: 'flags'
+            type: 'u16'
+          },
+          {
+            name: 'paused'
+            type: 'u16'
+          },
+        ]
+      }
+    },
+    {
+      name: 'programUnpaused'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'admin'
+            type: 'pubkey'
+          },
+          {
+            name: 'flags'
+            type: 'u16'
+          },
+          {
+            name: 'paused'
+            type: 'u16'
+          },
+        ]
+      }
+    },
+    {
+      name: 'raffleAccount'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffleId'
+            type: 'u64'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'isSoldOut'
+            type: 'bool'
+          },
+          {
+            name: 'progress'
+            type: 'u32'
+          },
+          {
+            name: 'totalEntries'
+            type: 'u64'
+          },
+          {
+            name: 'paymentMint'
+            type: 'pubkey'
+          },
+          {
+            name: 'itemName'
+            type: 'string'
+          },
+          {
+            name: 'itemDescription'
+            type: 'string'
+          },
+          {
+            name: 'itemImageUri'
+            type: 'string'
+          },
+          {
+            name: 'sellingPrice'
+            type: 'u64'
+          },
+          {
+            name: 'ticketPrice'
+            type: 'u64'
+          },
+          {
+            name: 'minTickets'
+            type: 'u32'
+          },
+          {
+            name: 'maxTickets'
+            type: 'u32'
+          },
+          {
+            name: 'deadline'
+            type: 'i64'
+          },
+          {
+            name: 'participantCount'
+            type: 'u32'
+          },
+          {
+            name: 'currentPage'
+            type: 'u32'
+          },
+          {
+            name: 'totalCollected'
+            type: 'u64'
+          },
+          {
+            name: 'totalRefunded'
+            type: 'u64'
+          },
+          {
+            name: 'status'
+            type: {
+              defined: {
+                name: 'raffleStatus'
+              }
+            }
+          },
+          {
+            name: 'randomnessSource'
+            type: {
+              defined: {
+                name: 'randomnessSource'
+              }
+            }
+          },
+          {
+            name: 'randomnessAccount'
+            type: {
+              option: 'pubkey'
+            }
+          },
+          {
+            name: 'randomnessCommitSlot'
+            type: {
+              option: 'u64'
+            }
+          },
+          {
+            name: 'winner'
+            type: {
+              option: 'pubkey'
+            }
+          },
+          {
+            name: 'winningTicket'
+            type: {
+              option: 'u64'
+            }
+          },
+          {
+            name: 'claimed'
+            type: 'bool'
+          },
+          {
+            name: 'bump'
+            type: 'u8'
+          },
+          {
+            name: 'escrowBump'
+            type: 'u8'
+          },
+          {
+            name: 'productDeliveredStatus'
+            type: {
+              defined: {
+                name: 'deliveryStatus'
+              }
+            }
+          },
+          {
+            name: 'trackingInfo'
+            type: {
+              option: 'string'
+            }
+          },
+          {
+            name: 'shippingDeadline'
+            type: {
+              option: 'i64'
+            }
+          },
+          {
+            name: 'shippedAt'
+            type: {
+              option: 'i64'
+            }
+          },
+          {
+            name: 'desputeDeadline'
+            type: {
+              option: 'i64'
+            }
+          },
+          {
+            name: 'payoutReleased'
+            type: 'bool'
+          },
+          {
+            name: 'feeBps'
+            type: 'u16'
+          },
+          {
+            name: 'treasury'
+            type: 'pubkey'
+          },
+          {
+            name: 'feeCollected'
+            type: 'u64'
+          },
+          {
+            name: 'bondAmount'
+            type: 'u64'
+          },
+          {
+            name: 'bondStatus'
+            type: {
+              defined: {
+                name: 'bondStatus'
+              }
+            }
+          },
+          {
+            name: 'disputeEvidenceHash'
+            type: {
+              option: {
+                array: ['u8', 32]
+              }
+            }
+          },
+          {
+            name: 'disputedAt'
+            type: {
+              option: 'i64'
+            }
+          },
+          {
+            name: 'disputeResolution'
+            type: {
+              option: {
+                defined: {
+                  name: 'disputeResolution'
+                }
+              }
+            }
+          },
+          {
+            name: 'entropy'
+            type: {
+              array: ['u8', 32]
+            }
+          },
+          {
+            name: 'entropyCommitments'
+            type: 'u32'
+          },
+          {
+            name: 'entropyReveals'
+            type: 'u32'
+          },
+        ]
+      }
     },
     {
-      code: 6022
-      name: 'underFlow'
-      msg: 'Arithmetic under flow'
+      name: 'raffleArchive'
+      docs: [
+        'Compact summary left behind by `close_raffle` when the seller asks for',
+        'one, so the history survives the raffle account being closed.',
+      ]
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'raffleId'
+            type: 'u64'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'paymentMint'
+            type: 'pubkey'
+          },
+          {
+            name: 'status'
+            type: {
+              defined: {
+                name: 'raffleStatus'
+              }
+            }
+          },
+          {
+            name: 'winner'
+            type: {
+              option: 'pubkey'
+            }
+          },
+          {
+            name: 'winningTicket'
+            type: {
+              option: 'u64'
+            }
+          },
+          {
+            name: 'totalEntries'
+            type: 'u64'
+          },
+          {
+            name: 'participantCount'
+            type: 'u32'
+          },
+          {
+            name: 'totalCollected'
+            type: 'u64'
+          },
+          {
+            name: 'totalRefunded'
+            type: 'u64'
+          },
+          {
+            name: 'feeCollected'
+            type: 'u64'
+          },
+          {
+            name: 'bondAmount'
+            type: 'u64'
+          },
+          {
+            name: 'bondStatus'
+            type: {
+              defined: {
+                name: 'bondStatus'
+              }
+            }
+          },
+          {
+            name: 'closedAt'
+            type: 'i64'
+          },
+          {
+            name: 'bump'
+            type: 'u8'
+          },
+        ]
+      }
     },
     {
-      code: 6023
-      name: 'raffleFull'
-      msg: 'Participants full!'
+      name: 'raffleCancelled'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'raffleId'
+            type: 'u64'
+          },
+        ]
+      }
     },
     {
-      code: 6024
-      name: 'randomnessExpired'
-      msg: 'Randomness expired!'
+      name: 'raffleClosed'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'raffleId'
+            type: 'u64'
+          },
+          {
+            name: 'status'
+            type: {
+              defined: {
+                name: 'raffleStatus'
+              }
+            }
+          },
+          {
+            name: 'archived'
+            type: 'bool'
+          },
+        ]
+      }
     },
     {
-      code: 6025
-      name: 'randomnessAlreadyRevealed'
-      msg: 'Randomness already expired!'
+      name: 'raffleCreated'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'ticketPrice'
+            type: 'u64'
+          },
+          {
+            name: 'deadline'
+            type: 'i64'
+          },
+          {
+            name: 'bondAmount'
+            type: 'u64'
+          },
+        ]
+      }
     },
     {
-      code: 6026
-      name: 'invalidRaffleState'
-      msg: 'Invalid Raffle state!'
+      name: 'raffleSettled'
+      type: {
+        kind: 'struct'
+        fields: [
+          {
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'seller'
+            type: 'pubkey'
+          },
+          {
+            name: 'totalCollected'
+            type: 'u64'
+          },
+          {
+            name: 'totalRefunded'
+            type: 'u64'
+          },
+          {
+            name: 'fee'
+            type: 'u64'
+          },
+          {
+            name: 'leftover'
+            type: 'u64'
+          },
+          {
+            name: 'settledAt'
+            type: 'i64'
+          },
+        ]
+      }
     },
     {
-      code: 6027
-      name: 'randomnessNotResolved'
-      msg: 'Randomness not resolved!'
+      name: 'raffleStatus'
+      type: {
+        kind: 'enum'
+        variants: [
+          {
+            name: 'active'
+          },
+          {
+            name: 'drawing'
+          },
+          {
+            name: 'completed'
+          },
+          {
+            name: 'cancelled'
+          },
+          {
+            name: 'refunded'
+          },
+          {
+            name: 'refunding'
+          },
+        ]
+      }
     },
-  ]
-  types: [
     {
-      name: 'counter'
+      name: 'randomnessRerequested'
       type: {
         kind: 'struct'
         fields: [
           {
-            name: 'counter'
+            name: 'raffle'
+            type: 'pubkey'
+          },
+          {
+            name: 'abandonedRandomnessAccount'
+            type: 'pubkey'
+          },
+          {
+            name: 'abandonedCommitSlot'
+            type: 'u64'
+          },
+          {
+            name: 'randomnessAccount'
+            type: 'pubkey'
+          },
+          {
+            name: 'commitSlot'
             type: 'u64'
           },
         ]
       }
     },
     {
-      name: 'deliveryStatus'
+      name: 'randomnessSource'
       type: {
         kind: 'enum'
         variants: [
           {
-            name: 'pending'
-          },
-          {
-            name: 'shipped'
-          },
-          {
-            name: 'delivered'
+            name: 'switchboard'
           },
           {
-            name: 'disputed'
+            name: 'commitReveal'
           },
           {
-            name: 'resolved'
+            name: 'deterministic'
           },
         ]
       }
     },
     {
-      name: 'productDelivered'
+      name: 'refundClaimed'
       type: {
         kind: 'struct'
         fields: [
@@ -576,20 +3169,26 @@ export type Raffle = {
             type: 'pubkey'
           },
           {
-            name: 'winner'
+            name: 'buyer'
             type: 'pubkey'
           },
           {
-            name: 'deliveredAt'
-            type: {
-              option: 'i64'
-            }
+            name: 'tickets'
+            type: 'u32'
+          },
+          {
+            name: 'amount'
+            type: 'u64'
+          },
+          {
+            name: 'totalRefunded'
+            type: 'u64'
           },
         ]
       }
     },
     {
-      name: 'productShipped'
+      name: 'salesClosed'
       type: {
         kind: 'struct'
         fields: [
@@ -598,230 +3197,259 @@ export type Raffle = {
             type: 'pubkey'
           },
           {
-            name: 'winner'
-            type: 'pubkey'
+            name: 'totalEntries'
+            type: 'u64'
           },
           {
-            name: 'shippedAt'
+            name: 'minTickets'
+            type: 'u32'
+          },
+          {
+            name: 'status'
             type: {
-              option: 'i64'
+              defined: {
+                name: 'raffleStatus'
+              }
             }
           },
+          {
+            name: 'closedAt'
+            type: 'i64'
+          },
         ]
       }
     },
     {
-      name: 'raffleAccount'
+      name: 'sellerPaid'
       type: {
         kind: 'struct'
         fields: [
           {
-            name: 'raffleId'
-            type: 'u64'
+            name: 'raffle'
+            type: 'pubkey'
           },
           {
             name: 'seller'
             type: 'pubkey'
           },
           {
-            name: 'isSoldOut'
-            type: 'bool'
+            name: 'amount'
+            type: 'u64'
           },
           {
-            name: 'progress'
-            type: 'u32'
+            name: 'fee'
+            type: 'u64'
           },
           {
-            name: 'totalEntries'
+            name: 'bondReturned'
             type: 'u64'
           },
           {
-            name: 'paymentMint'
+            name: 'treasury'
             type: 'pubkey'
           },
           {
-            name: 'itemName'
-            type: 'string'
+            name: 'sellingPrice'
+            type: 'u64'
           },
           {
-            name: 'itemDescription'
-            type: 'string'
+            name: 'surplus'
+            type: 'u64'
           },
+        ]
+      }
+    },
+    {
+      name: 'shippingExpired'
+      type: {
+        kind: 'struct'
+        fields: [
           {
-            name: 'itemImageUri'
-            type: 'string'
+            name: 'raffle'
+            type: 'pubkey'
           },
           {
-            name: 'sellingPrice'
-            type: 'u64'
+            name: 'seller'
+            type: 'pubkey'
           },
           {
-            name: 'ticketPrice'
-            type: 'u64'
+            name: 'winner'
+            type: 'pubkey'
           },
           {
-            name: 'minTickets'
-            type: 'u32'
+            name: 'shippingDeadline'
+            type: 'i64'
           },
           {
-            name: 'maxTickets'
-            type: 'u32'
+            name: 'bondSlashed'
+            type: 'u64'
           },
           {
-            name: 'deadline'
+            name: 'expiredAt'
             type: 'i64'
           },
+        ]
+      }
+    },
+    {
+      name: 'ticketRange'
+      type: {
+        kind: 'struct'
+        fields: [
           {
-            name: 'participants'
-            type: {
-              vec: 'pubkey'
-            }
+            name: 'buyer'
+            type: 'pubkey'
           },
           {
-            name: 'totalCollected'
+            name: 'firstTicket'
             type: 'u64'
           },
           {
-            name: 'status'
-            type: {
-              defined: {
-                name: 'raffleStatus'
-              }
-            }
+            name: 'tickets'
+            type: 'u32'
           },
+        ]
+      }
+    },
+    {
+      name: 'ticketReceipt'
+      docs: ['One per buyer per raffle, created on the first `buy_tickets` and topped up', 'on every later purchase.']
+      type: {
+        kind: 'struct'
+        fields: [
           {
-            name: 'randomnessAccount'
-            type: {
-              option: 'pubkey'
-            }
+            name: 'raffle'
+            type: 'pubkey'
           },
           {
-            name: 'randomnessCommitSlot'
-            type: {
-              option: 'u64'
-            }
+            name: 'buyer'
+            type: 'pubkey'
           },
           {
-            name: 'winner'
-            type: {
-              option: 'pubkey'
-            }
+            name: 'tickets'
+            type: 'u32'
           },
           {
-            name: 'claimed'
-            type: 'bool'
+            name: 'totalPaid'
+            type: 'u64'
           },
           {
-            name: 'bump'
-            type: 'u8'
+            name: 'firstTicket'
+            type: 'u64'
           },
           {
-            name: 'escrowBump'
-            type: 'u8'
+            name: 'firstPurchasedAt'
+            type: 'i64'
           },
           {
-            name: 'productDeliveredStatus'
-            type: {
-              defined: {
-                name: 'deliveryStatus'
-              }
-            }
+            name: 'lastPurchasedAt'
+            type: 'i64'
           },
           {
-            name: 'trackingInfo'
-            type: {
-              option: 'string'
-            }
+            name: 'refundClaimed'
+            type: 'bool'
           },
           {
-            name: 'shippedAt'
+            name: 'entropyCommitment'
             type: {
-              option: 'i64'
+              option: {
+                array: ['u8', 32]
+              }
             }
           },
           {
-            name: 'desputeDeadline'
-            type: {
-              option: 'i64'
-            }
+            name: 'entropyRevealed'
+            type: 'bool'
+          },
+          {
+            name: 'bump'
+            type: 'u8'
           },
         ]
       }
     },
     {
-      name: 'raffleCreated'
+      name: 'ticketsBought'
       type: {
         kind: 'struct'
         fields: [
           {
-            name: 'raffle'
+            name: 'buyer'
             type: 'pubkey'
           },
           {
-            name: 'seller'
+            name: 'raffle'
             type: 'pubkey'
           },
           {
-            name: 'ticketPrice'
+            name: 'numberOfTicketsBought'
+            type: 'u8'
+          },
+          {
+            name: 'totalTicketsNow'
             type: 'u64'
           },
           {
-            name: 'deadline'
-            type: 'i64'
+            name: 'totalParticipantsNow'
+            type: 'u32'
           },
         ]
       }
     },
     {
-      name: 'raffleStatus'
+      name: 'winnerDrawn'
       type: {
-        kind: 'enum'
-        variants: [
+        kind: 'struct'
+        fields: [
           {
-            name: 'active'
+            name: 'raffle'
+            type: 'pubkey'
           },
           {
-            name: 'drawing'
+            name: 'randomnessSource'
+            type: {
+              defined: {
+                name: 'randomnessSource'
+              }
+            }
           },
           {
-            name: 'completed'
+            name: 'randomnessAccount'
+            type: 'pubkey'
           },
           {
-            name: 'cancelled'
+            name: 'randomness'
+            type: {
+              array: ['u8', 32]
+            }
           },
           {
-            name: 'refunded'
+            name: 'seedSlot'
+            type: 'u64'
           },
           {
-            name: 'ended'
+            name: 'revealSlot'
+            type: 'u64'
           },
-        ]
-      }
-    },
-    {
-      name: 'ticketsBought'
-      type: {
-        kind: 'struct'
-        fields: [
           {
-            name: 'buyer'
-            type: 'pubkey'
+            name: 'totalEntries'
+            type: 'u64'
           },
           {
-            name: 'raffle'
-            type: 'pubkey'
+            name: 'winningTicket'
+            type: 'u64'
           },
           {
-            name: 'numberOfTicketsBought'
-            type: 'u8'
+            name: 'winner'
+            type: 'pubkey'
           },
           {
-            name: 'totalTicketsNow'
-            type: 'u64'
+            name: 'participantPage'
+            type: 'u32'
           },
           {
-            name: 'totalParticipantsNow'
-            type: 'u32'
+            name: 'algorithmVersion'
+            type: 'u8'
           },
         ]
       }
@@ -9,9 +9,13 @@ export interface CreateRaffleInputs {
   minTickets: number | null
   maxTickets: number | null
   deadline: number
+  // Defaults to 'switchboard'; 'commitReveal' draws from secrets the buyers commit and reveal
+  randomnessSource?: RandomnessSource
 }
 
-export type RaffleStatus = 'active' | 'drawing' | 'completed' | 'cancelled' | 'refunded' | 'ended'
+export type RandomnessSource = 'switchboard' | 'commitReveal'
+
+export type RaffleStatus = 'active' | 'drawing' | 'completed' | 'cancelled' | 'refunding' | 'refunded'
 
 export interface buyTicketProps {
   numTickets: number